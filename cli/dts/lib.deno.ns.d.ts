@@ -132,6 +132,12 @@ declare namespace Deno {
     /** Ensure the test case does not prematurely cause the process to exit,
      * for example via a call to `Deno.exit`. Defaults to true. */
     sanitizeExit?: boolean;
+
+    /** Fail the test if it runs for longer than the given number of
+     * milliseconds. The failure lists the resources opened by the test, which
+     * are then closed. Defaults to the value of the `--timeout` flag of
+     * `deno test`, or no timeout. */
+    timeout?: number;
   }
 
   /** Register a test which will be run when `deno test` is used on the command
@@ -1869,6 +1875,26 @@ declare namespace Deno {
     [Symbol.asyncIterator](): AsyncIterableIterator<FsEvent>;
   }
 
+  export interface WatchFsOptions {
+    /** Watch directories and all of their sub directories. */
+    recursive: boolean;
+    /** Coalesce events that arrive in quick succession: events are held back
+     * until none arrived for `debounce` milliseconds (but at most ten times as
+     * long), and consecutive events of the same kind are merged into one. */
+    debounce?: number;
+    /** Only report paths matching one of these globs. Globs that are not
+     * absolute are matched against paths relative to the watched path, at any
+     * depth, and a path also matches when one of its parent directories below
+     * the watched path does. Supports `*`, `**`, `?`, `[...]` and `{a,b}`. */
+    include?: string[];
+    /** Never report paths matching one of these globs, using the same
+     * matching rules as `include`. */
+    exclude?: string[];
+    /** Drop `modify` events for files whose contents did not change since
+     * the last event for them. Defaults to `false`. */
+    dedupe?: boolean;
+  }
+
   /** Watch for file system events against one or more `paths`, which can be files
    * or directories.  These paths must exist already.  One user action (e.g.
    * `touch test.file`) can  generate multiple file system events.  Likewise,
@@ -1900,10 +1926,23 @@ declare namespace Deno {
    *    console.log(">>>> event", event);
    * }
    * ```
+   *
+   * Bursts of events, such as an editor saving many files, can be coalesced
+   * and filtered before they reach JavaScript:
+   *
+   * ```ts
+   * const watcher = Deno.watchFs("./src", {
+   *   recursive: true,
+   *   debounce: 100,
+   *   include: ["*.ts"],
+   *   exclude: ["**/node_modules/**"],
+   *   dedupe: true,
+   * });
+   * ```
    */
   export function watchFs(
     paths: string | string[],
-    options?: { recursive: boolean },
+    options?: WatchFsOptions,
   ): FsWatcher;
 
   export class Process<T extends RunOptions = RunOptions> {
@@ -2141,6 +2180,13 @@ declare namespace Deno {
     | PluginPermissionDescriptor
     | HrtimePermissionDescriptor;
 
+  export interface PermissionRevokeOptions {
+    /** Whether the revocation is also applied to all child workers (and their
+     * children), so that they can no longer use the permission either.
+     * Defaults to `true`. */
+    propagate?: boolean;
+  }
+
   export interface PermissionStatusEventMap {
     "change": Event;
   }
@@ -2188,6 +2234,11 @@ declare namespace Deno {
     query(desc: PermissionDescriptor): Promise<PermissionStatus>;
 
     /** Revokes a permission, and resolves to the state of the permission.
+     * Unless `options.propagate` is `false`, the revocation is also applied to
+     * all workers spawned from this thread. Workers pick up the revocation
+     * asynchronously, on the next turn of their event loop, so code already
+     * running in a worker may still use the permission until it yields; the
+     * returned promise does not wait for workers to apply it.
      *
      * ```ts
      * import { assert } from "https://deno.land/std/testing/asserts.ts";
@@ -2196,7 +2247,10 @@ declare namespace Deno {
      * assert(status.state !== "granted")
      * ```
      */
-    revoke(desc: PermissionDescriptor): Promise<PermissionStatus>;
+    revoke(
+      desc: PermissionDescriptor,
+      options?: PermissionRevokeOptions,
+    ): Promise<PermissionStatus>;
 
     /** Requests the permission, and resolves to the state of the permission.
      *
@@ -780,6 +780,32 @@ declare namespace Deno {
     options?: SetRawOptions,
   ): void;
 
+  export interface OpenBlobOptions {
+    /** The MIME type of the returned `File`. Defaults to `""`. */
+    type?: string;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Creates a `File` whose contents are read from disk lazily, only when the
+   * blob (or a slice of it) is read. Slicing the returned `File` does not read
+   * the file. The file's name is the last path component and its
+   * `lastModified` is the file's modification time.
+   *
+   * The contents are not snapshotted: if the file changes on disk after this
+   * call, reads will observe the change, and fail if it got shorter.
+   *
+   * ```ts
+   * const file = await Deno.openBlob("./video.mp4", { type: "video/mp4" });
+   * await fetch("https://example.com/upload", { method: "PUT", body: file });
+   * ```
+   *
+   * Requires `allow-read` permission. */
+  export function openBlob(
+    path: string | URL,
+    options?: OpenBlobOptions,
+  ): Promise<File>;
+
   /** **UNSTABLE**: needs investigation into high precision time.
    *
    * Synchronously changes the access (`atime`) and modification (`mtime`) times
@@ -866,9 +892,35 @@ declare namespace Deno {
      */
     caData?: string;
     proxy?: Proxy;
+    /** Pins hostnames to IP addresses, bypassing DNS for them. The port is
+     * always taken from the request URL. Each request to a pinned hostname
+     * requires net permission for both the hostname and the address, on the
+     * request's port.
+     *
+     * ```ts
+     * const client = Deno.createHttpClient({
+     *   resolve: { "example.com": "127.0.0.1" },
+     * });
+     * ```
+     */
+    resolve?: Record<string, string>;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   * Progress of a request body upload, as reported to `onUploadProgress`.
+   */
+  export interface UploadProgress {
+    /** The number of body bytes sent so far. */
+    loaded: number;
+    /** The total body size, or `null` if it is not known upfront. */
+    total: number | null;
   }
 
   export interface Proxy {
+    /** The proxy URL. Supported schemes are `http:`, `https:`, `socks5:` and
+     * `socks5h:`. With `socks5h:` hostnames are resolved by the proxy rather
+     * than locally.
+     */
     url: string;
     basicAuth?: BasicAuth;
   }
@@ -890,6 +942,16 @@ declare namespace Deno {
    * const client = Deno.createHttpClient({ proxy: { url: "http://myproxy.com:8080" } });
    * const response = await fetch("https://myserver.com", { client });
    * ```
+   *
+   * ```ts
+   * const client = Deno.createHttpClient({
+   *   proxy: {
+   *     url: "socks5h://127.0.0.1:9050",
+   *     basicAuth: { username: "user", password: "pass" },
+   *   },
+   * });
+   * const response = await fetch("https://myserver.com", { client });
+   * ```
    */
   export function createHttpClient(
     options: CreateHttpClientOptions,
@@ -958,6 +1020,72 @@ declare namespace Deno {
     bytesReceived: number;
   }
 
+  /** **UNSTABLE**: New option, yet to be vetted. */
+  export interface RunOptions {
+    /** Attach the subprocess' stdin, stdout and stderr to a new
+     * pseudo-terminal, so that it behaves as if it was run interactively.
+     * Pass an object to set the initial size, which defaults to 24 rows and
+     * 80 columns. The terminal is available as `Process.pty`; the `stdin`,
+     * `stdout` and `stderr` options can't be combined with it.
+     *
+     * Not supported on Windows. */
+    pty?: boolean | PtySize;
+    /** Spawn the subprocess in a new process group, which the processes it
+     * spawns in turn inherit, so that they can all be signalled at once with
+     * `Process.killTree()`. On Windows the group is a job object. Defaults to
+     * `false`. */
+    processGroup?: boolean;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted. */
+  export interface PtySize {
+    rows: number;
+    cols: number;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * The controlling side of a pseudo-terminal that a subprocess was spawned
+   * with. Reading returns the subprocess' output, and reaches EOF once it has
+   * exited; writing sends it input as if typed.
+   *
+   * ```ts
+   * const p = Deno.run({ cmd: ["python3"], pty: true });
+   * await p.pty!.write(new TextEncoder().encode("print(1 + 1)\n"));
+   * ```
+   */
+  export interface Pty extends Reader, Writer, Closer {
+    readonly rid: number;
+    /** Changes the terminal size, which sends `SIGWINCH` to the
+     * subprocess. */
+    resize(size: PtySize): void;
+    /** Switches the terminal in or out of raw mode, in which input is passed
+     * to the subprocess as is, without line editing or echoing. */
+    setRaw(mode: boolean): void;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted. */
+  export interface Process<T extends RunOptions = RunOptions> {
+    /** The pseudo-terminal the process was spawned with, if `pty` was set. */
+    readonly pty?: Pty;
+    /** Send a signal to every process in the subprocess' process group,
+     * including processes it spawned. Requires `allow-run` permission, and
+     * the process to have been spawned with `processGroup: true`.
+     *
+     * On Windows only `SIGINT`, `SIGKILL` and `SIGTERM` are accepted, all of
+     * which terminate the job object.
+     *
+     * ```ts
+     * const p = Deno.run({
+     *   cmd: ["sh", "-c", "sleep 100 & wait"],
+     *   processGroup: true,
+     * });
+     * p.killTree(Deno.Signal.SIGTERM);
+     * ```
+     */
+    killTree(signo: number): void;
+  }
+
   /** **UNSTABLE**: New option, yet to be vetted. */
   export interface TestDefinition {
     /** Specifies the permissions that should be used to run the test.
@@ -1117,6 +1245,52 @@ declare namespace Deno {
    * then the underlying HttpConn resource is closed automatically.
    */
   export function serveHttp(conn: Conn): HttpConn;
+
+  /** **UNSTABLE**: new API, yet to be vetted.
+   *
+   * Sends files, connections and listeners to the process on the other end of
+   * a Unix socket connection, along with `data`, which must not be empty and
+   * defaults to a single zero byte. The resources stay open in this process
+   * as well. Resolves to the number of bytes of `data` written.
+   *
+   * ```ts
+   * const conn = await Deno.connect({ path: "/tmp/worker.sock", transport: "unix" });
+   * const listener = Deno.listen({ port: 8080 });
+   * await Deno.sendResources(conn, [listener]);
+   * ```
+   *
+   * Not supported on Windows.
+   */
+  export function sendResources(
+    conn: Conn,
+    resources: Array<File | Conn | Listener>,
+    data?: Uint8Array,
+  ): Promise<number>;
+
+  /** **UNSTABLE**: new API, yet to be vetted.
+   *
+   * Receives data into `buffer`, along with any resources that were sent with
+   * it using `Deno.sendResources()`. `nread` is `null` at EOF. Sockets are
+   * received as a `Conn` or `Listener`; anything else, such as a pipe, is
+   * received as a `File`. At most 16 resources are received at once; if more
+   * were sent, the rest are closed and `truncated` is `true`.
+   *
+   * ```ts
+   * const listener = Deno.listen({ path: "/tmp/worker.sock", transport: "unix" });
+   * const conn = await listener.accept();
+   * const { resources: [httpListener] } = await Deno.receiveResources(conn);
+   * ```
+   *
+   * Not supported on Windows.
+   */
+  export function receiveResources(
+    conn: Conn,
+    buffer?: Uint8Array,
+  ): Promise<{
+    nread: number | null;
+    resources: Array<File | Conn | Listener>;
+    truncated: boolean;
+  }>;
 }
 
 declare function fetch(
@@ -1124,6 +1298,27 @@ declare function fetch(
   init?: RequestInit & { client: Deno.HttpClient },
 ): Promise<Response>;
 
+declare interface RequestInit {
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Called each time a part of the request body has been handed to the
+   * network. `total` is the body size, or `null` if it isn't known upfront
+   * (for example for a `ReadableStream` body). Throwing from the callback
+   * cancels the upload, and `fetch` rejects with the thrown error.
+   *
+   * ```ts
+   * await fetch("https://example.com/upload", {
+   *   method: "PUT",
+   *   body: await Deno.openBlob("./big.bin"),
+   *   onUploadProgress({ loaded, total }) {
+   *     console.log(`${loaded} / ${total} bytes`);
+   *   },
+   * });
+   * ```
+   */
+  onUploadProgress?: (progress: Deno.UploadProgress) => void;
+}
+
 declare interface WorkerOptions {
   /** UNSTABLE: New API.
    *
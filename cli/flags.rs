@@ -45,6 +45,10 @@ pub enum DenoSubcommand {
     output: Option<PathBuf>,
     args: Vec<String>,
     target: Option<String>,
+    windows_icon: Option<PathBuf>,
+    windows_subsystem: Option<String>,
+    windows_version: Option<String>,
+    windows_metadata: Vec<String>,
   },
   Completions {
     buf: Box<[u8]>,
@@ -59,6 +63,8 @@ pub enum DenoSubcommand {
   Doc {
     private: bool,
     json: bool,
+    markdown: bool,
+    out_dir: Option<PathBuf>,
     source_file: Option<String>,
     filter: Option<String>,
   },
@@ -104,6 +110,8 @@ pub enum DenoSubcommand {
     include: Option<Vec<String>>,
     filter: Option<String>,
     shuffle: Option<u64>,
+    shard: Option<(usize, usize)>,
+    timeout: Option<u64>,
     concurrent_jobs: usize,
   },
   Types,
@@ -347,7 +355,7 @@ pub fn flags_from_vec(args: Vec<String>) -> clap::Result<Flags> {
   } else if let Some(m) = matches.subcommand_matches("upgrade") {
     upgrade_parse(&mut flags, m);
   } else if let Some(m) = matches.subcommand_matches("doc") {
-    doc_parse(&mut flags, m);
+    doc_parse(&mut flags, m)?;
   } else if let Some(m) = matches.subcommand_matches("lint") {
     lint_parse(&mut flags, m);
   } else if let Some(m) = matches.subcommand_matches("compile") {
@@ -485,6 +493,38 @@ fn compile_subcommand<'a, 'b>() -> App<'a, 'b> {
         .takes_value(true)
         .possible_values(&["x86_64-unknown-linux-gnu", "x86_64-pc-windows-msvc", "x86_64-apple-darwin", "aarch64-apple-darwin"])
     )
+    .arg(
+      Arg::with_name("windows-icon")
+        .long("windows-icon")
+        .help("Icon (.ico file) of the Windows executable")
+        .takes_value(true)
+        .value_name("FILE")
+    )
+    .arg(
+      Arg::with_name("windows-subsystem")
+        .long("windows-subsystem")
+        .help("Subsystem of the Windows executable")
+        .takes_value(true)
+        .possible_values(&["console", "windows"])
+    )
+    .arg(
+      Arg::with_name("windows-version")
+        .long("windows-version")
+        .help("File and product version of the Windows executable")
+        .takes_value(true)
+        .value_name("VERSION")
+        .validator(|v| crate::tools::pe::parse_version(&v).map(|_| ()).map_err(|e| e.to_string()))
+    )
+    .arg(
+      Arg::with_name("windows-metadata")
+        .long("windows-metadata")
+        .help("Version information string of the Windows executable")
+        .takes_value(true)
+        .multiple(true)
+        .number_of_values(1)
+        .value_name("KEY=VALUE")
+        .validator(|v| if v.contains('=') { Ok(()) } else { Err(format!("Invalid metadata \"{}\", expected KEY=VALUE", v)) })
+    )
     .about("UNSTABLE: Compile the script into a self contained executable")
     .long_about(
       "UNSTABLE: Compiles the given script into a self contained executable.
@@ -507,6 +547,12 @@ The executable name is inferred by default:
 This commands supports cross-compiling to different target architectures using `--target` flag.
 On the first invocation with deno will download proper binary and cache it in $DENO_DIR. The
 aarch64-apple-darwin target is not supported in canary.
+
+When compiling for Windows, the icon, version information and subsystem of the executable can
+be set, also when cross-compiling:
+
+  deno compile --target x86_64-pc-windows-msvc --windows-icon app.ico --windows-version 1.2.0 \\
+    --windows-metadata ProductName=App --windows-subsystem windows app.ts
 ",
     )
 }
@@ -624,6 +670,12 @@ Output documentation in JSON format:
 
     deno doc --json ./path/to/module.ts
 
+Output documentation as Markdown, either as a single document or as an index
+plus one file per symbol:
+
+    deno doc --output=markdown ./path/to/module.ts
+    deno doc --output=markdown --out-dir=docs ./path/to/module.ts
+
 Target a specific symbol:
 
     deno doc ./path/to/module.ts MyClass.someField
@@ -641,6 +693,24 @@ Show documentation for runtime built-ins:
         .help("Output documentation in JSON format")
         .takes_value(false),
     )
+    .arg(
+      Arg::with_name("output")
+        .long("output")
+        .value_name("FORMAT")
+        .help("Output documentation in the given format")
+        .possible_values(&["json", "markdown"])
+        .require_equals(true)
+        .takes_value(true)
+        .conflicts_with("json"),
+    )
+    .arg(
+      Arg::with_name("out-dir")
+        .long("out-dir")
+        .value_name("DIR")
+        .help("Write markdown output to files in the given directory")
+        .requires("output")
+        .takes_value(true),
+    )
     .arg(
       Arg::with_name("private")
         .long("private")
@@ -1046,6 +1116,39 @@ fn test_subcommand<'a, 'b>() -> App<'a, 'b> {
           Err(_) => Err("Shuffle seed should be a number".to_string()),
         }),
     )
+    .arg(
+      Arg::with_name("shard")
+        .long("shard")
+        .value_name("INDEX/COUNT")
+        .help("Only run the INDEX-th of COUNT partitions of the tests")
+        .long_help(
+          "Only run the INDEX-th of COUNT partitions of the tests, e.g. \
+--shard=1/3. Tests are assigned to partitions by a stable hash of their name, \
+so the same test always runs in the same partition.",
+        )
+        .require_equals(true)
+        .takes_value(true)
+        .validator(|val: String| parse_shard(&val).map(|_| ())),
+    )
+    .arg(
+      Arg::with_name("timeout")
+        .long("timeout")
+        .value_name("MS")
+        .help("Default timeout for each test in milliseconds")
+        .long_help(
+          "Fail any test that runs for longer than the given number of \
+milliseconds. Applies to tests that don't set their own `timeout` option.",
+        )
+        .require_equals(true)
+        .takes_value(true)
+        .validator(|val: String| match val.parse::<u64>() {
+          Ok(ms) if ms > 0 && ms <= i32::MAX as u64 => Ok(()),
+          _ => Err(format!(
+            "Timeout should be a positive number no greater than {}",
+            i32::MAX
+          )),
+        }),
+    )
     .arg(
       Arg::with_name("coverage")
         .long("coverage")
@@ -1194,6 +1297,13 @@ fn permission_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
         .use_delimiter(true)
         .require_equals(true)
         .help("Allow network access")
+        .long_help(
+          "Allow network access. Entries are hostnames with an optional port, \
+or CIDR ranges such as 10.0.0.0/8. For Deno.connect, Deno.connectTls and UDP \
+sends, a range also allows hostnames that resolve into it; fetch and \
+WebSocket only match ranges against IP addresses. vsock addresses are \
+allowed with vsock, vsock:<cid> or vsock:<cid>:<port>.",
+        )
         .validator(crate::flags_allow_net::validator),
     )
     .arg(
@@ -1220,7 +1330,19 @@ fn permission_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
         .takes_value(true)
         .use_delimiter(true)
         .require_equals(true)
-        .help("Allow running subprocesses"),
+        .help("Allow running subprocesses")
+        .long_help(
+          "Allow running subprocesses
+--allow-run
+  Allow running any subprocess
+--allow-run=deno,git
+  Allow running only the given executables
+--allow-run=\"git:status -s|log\"
+  Allow running git only when its arguments start with one of the given
+  prefixes. Separate alternative prefixes with '|': a comma starts a new
+  entry, so --allow-run=git:status,log would allow running any executable
+  named log",
+        ),
     )
     .arg(
       Arg::with_name("allow-plugin")
@@ -1491,12 +1613,24 @@ fn compile_parse(flags: &mut Flags, matches: &clap::ArgMatches) {
   let source_file = script[0].to_string();
   let output = matches.value_of("output").map(PathBuf::from);
   let target = matches.value_of("target").map(String::from);
+  let windows_icon = matches.value_of("windows-icon").map(PathBuf::from);
+  let windows_subsystem =
+    matches.value_of("windows-subsystem").map(String::from);
+  let windows_version = matches.value_of("windows-version").map(String::from);
+  let windows_metadata = matches
+    .values_of("windows-metadata")
+    .map(|values| values.map(String::from).collect())
+    .unwrap_or_default();
 
   flags.subcommand = DenoSubcommand::Compile {
     source_file,
     output,
     args,
     target,
+    windows_icon,
+    windows_subsystem,
+    windows_version,
+    windows_metadata,
   };
 }
 
@@ -1541,20 +1675,35 @@ fn coverage_parse(flags: &mut Flags, matches: &clap::ArgMatches) {
   };
 }
 
-fn doc_parse(flags: &mut Flags, matches: &clap::ArgMatches) {
+fn doc_parse(
+  flags: &mut Flags,
+  matches: &clap::ArgMatches,
+) -> clap::Result<()> {
   import_map_arg_parse(flags, matches);
   reload_arg_parse(flags, matches);
 
   let source_file = matches.value_of("source_file").map(String::from);
   let private = matches.is_present("private");
-  let json = matches.is_present("json");
+  let output = matches.value_of("output");
+  let json = matches.is_present("json") || output == Some("json");
+  let markdown = output == Some("markdown");
+  let out_dir = matches.value_of("out-dir").map(PathBuf::from);
+  if out_dir.is_some() && !markdown {
+    return Err(clap::Error::with_description(
+      "--out-dir can only be used with --output=markdown",
+      clap::ErrorKind::ArgumentConflict,
+    ));
+  }
   let filter = matches.value_of("filter").map(String::from);
   flags.subcommand = DenoSubcommand::Doc {
     source_file,
     json,
+    markdown,
+    out_dir,
     filter,
     private,
   };
+  Ok(())
 }
 
 fn eval_parse(flags: &mut Flags, matches: &clap::ArgMatches) {
@@ -1737,6 +1886,14 @@ fn test_parse(flags: &mut Flags, matches: &clap::ArgMatches) {
     None
   };
 
+  let shard = matches
+    .value_of("shard")
+    .map(|value| parse_shard(value).unwrap());
+
+  let timeout = matches
+    .value_of("timeout")
+    .map(|value| value.parse::<u64>().unwrap());
+
   if matches.is_present("script_arg") {
     let script_arg: Vec<String> = matches
       .values_of("script_arg")
@@ -1780,11 +1937,27 @@ fn test_parse(flags: &mut Flags, matches: &clap::ArgMatches) {
     include,
     filter,
     shuffle,
+    shard,
+    timeout,
     allow_none,
     concurrent_jobs,
   };
 }
 
+/// Parses a `--shard` value of the form `INDEX/COUNT`, where INDEX is 1-based.
+fn parse_shard(value: &str) -> Result<(usize, usize), String> {
+  let error = || format!("Invalid shard \"{}\", expected INDEX/COUNT", value);
+  let mut parts = value.splitn(2, '/');
+  let index = parts.next().and_then(|v| v.parse::<usize>().ok());
+  let count = parts.next().and_then(|v| v.parse::<usize>().ok());
+  match (index, count) {
+    (Some(index), Some(count)) if index >= 1 && index <= count => {
+      Ok((index, count))
+    }
+    _ => Err(error()),
+  }
+}
+
 fn types_parse(flags: &mut Flags, _matches: &clap::ArgMatches) {
   flags.subcommand = DenoSubcommand::Types;
 }
@@ -2781,6 +2954,26 @@ mod tests {
     );
   }
 
+  #[test]
+  fn allow_run_allowlist_with_args() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--allow-run=git:status|log,git:remote show",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run {
+          script: "script.ts".to_string(),
+        },
+        allow_run: Some(svec!["git:status|log", "git:remote show"]),
+        ..Flags::default()
+      }
+    );
+  }
+
   #[test]
   fn allow_env_allowlist() {
     let r =
@@ -3035,6 +3228,8 @@ mod tests {
           source_file: Some("script.ts".to_owned()),
           private: false,
           json: false,
+          markdown: false,
+          out_dir: None,
           filter: None,
         },
         import_map_path: Some("import_map.json".to_owned()),
@@ -3417,6 +3612,8 @@ mod tests {
           quiet: false,
           include: Some(svec!["dir1/", "dir2/"]),
           shuffle: None,
+          shard: None,
+          timeout: None,
           concurrent_jobs: 1,
         },
         unstable: true,
@@ -3429,6 +3626,65 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_shard() {
+    let r = flags_from_vec(svec!["deno", "test", "--shard=2/3"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test {
+          no_run: false,
+          doc: false,
+          fail_fast: None,
+          filter: None,
+          allow_none: false,
+          quiet: false,
+          include: None,
+          shuffle: None,
+          shard: Some((2, 3)),
+          timeout: None,
+          concurrent_jobs: 1,
+        },
+        ..Flags::default()
+      }
+    );
+
+    for invalid in &["--shard=0/3", "--shard=4/3", "--shard=1", "--shard=a/b"] {
+      let r = flags_from_vec(svec!["deno", "test", invalid]);
+      assert!(r.is_err());
+    }
+  }
+
+  #[test]
+  fn test_with_timeout() {
+    let r = flags_from_vec(svec!["deno", "test", "--timeout=500"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test {
+          no_run: false,
+          doc: false,
+          fail_fast: None,
+          filter: None,
+          allow_none: false,
+          quiet: false,
+          include: None,
+          shuffle: None,
+          shard: None,
+          timeout: Some(500),
+          concurrent_jobs: 1,
+        },
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "test", "--timeout=0"]);
+    assert!(r.is_err());
+
+    let r = flags_from_vec(svec!["deno", "test", "--timeout=2147483648"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn run_with_cafile() {
     let r = flags_from_vec(svec![
@@ -3464,6 +3720,8 @@ mod tests {
           allow_none: false,
           quiet: false,
           shuffle: None,
+          shard: None,
+          timeout: None,
           include: None,
           concurrent_jobs: 1,
         },
@@ -3567,6 +3825,8 @@ mod tests {
         subcommand: DenoSubcommand::Doc {
           private: false,
           json: true,
+          markdown: false,
+          out_dir: None,
           source_file: Some("path/to/module.ts".to_string()),
           filter: None,
         },
@@ -3586,6 +3846,8 @@ mod tests {
         subcommand: DenoSubcommand::Doc {
           private: false,
           json: false,
+          markdown: false,
+          out_dir: None,
           source_file: Some("path/to/module.ts".to_string()),
           filter: Some("SomeClass.someField".to_string()),
         },
@@ -3600,6 +3862,8 @@ mod tests {
         subcommand: DenoSubcommand::Doc {
           private: false,
           json: false,
+          markdown: false,
+          out_dir: None,
           source_file: None,
           filter: None,
         },
@@ -3614,6 +3878,8 @@ mod tests {
         subcommand: DenoSubcommand::Doc {
           private: false,
           json: false,
+          markdown: false,
+          out_dir: None,
           source_file: Some("--builtin".to_string()),
           filter: Some("Deno.Listener".to_string()),
         },
@@ -3621,6 +3887,40 @@ mod tests {
       }
     );
 
+    let r = flags_from_vec(svec![
+      "deno",
+      "doc",
+      "--output=markdown",
+      "--out-dir=docs",
+      "path/to/module.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Doc {
+          private: false,
+          json: false,
+          markdown: true,
+          out_dir: Some(PathBuf::from("docs")),
+          source_file: Some("path/to/module.ts".to_string()),
+          filter: None,
+        },
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "doc", "--out-dir=docs", "mod.ts"]);
+    assert!(r.is_err());
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "doc",
+      "--output=json",
+      "--out-dir=docs",
+      "mod.ts"
+    ]);
+    assert_eq!(r.unwrap_err().kind, clap::ErrorKind::ArgumentConflict);
+
     let r =
       flags_from_vec(svec!["deno", "doc", "--private", "path/to/module.js"]);
     assert_eq!(
@@ -3629,6 +3929,8 @@ mod tests {
         subcommand: DenoSubcommand::Doc {
           private: true,
           json: false,
+          markdown: false,
+          out_dir: None,
           source_file: Some("path/to/module.js".to_string()),
           filter: None,
         },
@@ -3667,6 +3969,10 @@ mod tests {
           output: None,
           args: vec![],
           target: None,
+          windows_icon: None,
+          windows_subsystem: None,
+          windows_version: None,
+          windows_metadata: vec![],
         },
         ..Flags::default()
       }
@@ -3685,6 +3991,10 @@ mod tests {
           output: Some(PathBuf::from("colors")),
           args: svec!["foo", "bar"],
           target: None,
+          windows_icon: None,
+          windows_subsystem: None,
+          windows_version: None,
+          windows_metadata: vec![],
         },
         import_map_path: Some("import_map.json".to_string()),
         no_remote: true,
@@ -3705,6 +4015,52 @@ mod tests {
     );
   }
 
+  #[test]
+  fn compile_with_windows_options() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "compile",
+      "--target",
+      "x86_64-pc-windows-msvc",
+      "--windows-icon",
+      "app.ico",
+      "--windows-subsystem",
+      "windows",
+      "--windows-version",
+      "1.2.3",
+      "--windows-metadata",
+      "ProductName=App",
+      "--windows-metadata",
+      "CompanyName=Example",
+      "app.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Compile {
+          source_file: "app.ts".to_string(),
+          output: None,
+          args: vec![],
+          target: Some("x86_64-pc-windows-msvc".to_string()),
+          windows_icon: Some(PathBuf::from("app.ico")),
+          windows_subsystem: Some("windows".to_string()),
+          windows_version: Some("1.2.3".to_string()),
+          windows_metadata: svec!["ProductName=App", "CompanyName=Example"],
+        },
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "compile",
+      "--windows-version",
+      "1.x",
+      "app.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn coverage() {
     let r = flags_from_vec(svec!["deno", "coverage", "foo.json"]);
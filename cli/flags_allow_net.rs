@@ -1,6 +1,7 @@
 // Copyright 2018-2021 the Deno authors. All rights reserved. MIT license.
 
 use deno_core::url::Url;
+use ipnet::IpNet;
 use std::net::IpAddr;
 use std::str::FromStr;
 
@@ -26,7 +27,28 @@ impl FromStr for BarePort {
   }
 }
 
+/// Returns true for vsock entries: `vsock`, `vsock:<cid>` or
+/// `vsock:<cid>:<port>`.
+fn is_vsock(entry: &str) -> bool {
+  let mut parts = entry.split(':');
+  parts.next() == Some("vsock")
+    && parts
+      .by_ref()
+      .take(2)
+      .all(|part| part.parse::<u32>().is_ok())
+    && parts.next().is_none()
+}
+
 pub fn validator(host_and_port: String) -> Result<(), String> {
+  if is_vsock(&host_and_port) {
+    return Ok(());
+  }
+  if host_and_port.contains('/') {
+    return match host_and_port.parse::<IpNet>() {
+      Ok(_) => Ok(()),
+      Err(_) => Err(format!("Bad CIDR range: {}", host_and_port)),
+    };
+  }
   if Url::parse(&format!("deno://{}", host_and_port)).is_ok()
     || host_and_port.parse::<IpAddr>().is_ok()
     || host_and_port.parse::<BarePort>().is_ok()
@@ -43,7 +65,9 @@ pub fn validator(host_and_port: String) -> Result<(), String> {
 pub fn parse(paths: Vec<String>) -> clap::Result<Vec<String>> {
   let mut out: Vec<String> = vec![];
   for host_and_port in paths.iter() {
-    if Url::parse(&format!("deno://{}", host_and_port)).is_ok()
+    if is_vsock(host_and_port)
+      || host_and_port.parse::<IpNet>().is_ok()
+      || Url::parse(&format!("deno://{}", host_and_port)).is_ok()
       || host_and_port.parse::<IpAddr>().is_ok()
     {
       out.push(host_and_port.to_owned())
@@ -181,6 +205,27 @@ mod tests {
     assert_eq!(actual, expected);
   }
 
+  #[test]
+  fn parse_net_args_cidr() {
+    let entries = svec!["10.0.0.0/8", "192.168.1.0/24", "fd00::/8"];
+    let expected = svec!["10.0.0.0/8", "192.168.1.0/24", "fd00::/8"];
+    let actual = parse(entries).unwrap();
+    assert_eq!(actual, expected);
+    assert!(super::validator("10.0.0.0/33".to_string()).is_err());
+    assert!(super::validator("deno.land/x".to_string()).is_err());
+  }
+
+  #[test]
+  fn parse_net_args_vsock() {
+    let entries = svec!["vsock", "vsock:2", "vsock:2:5000"];
+    let expected = svec!["vsock", "vsock:2", "vsock:2:5000"];
+    let actual = parse(entries).unwrap();
+    assert_eq!(actual, expected);
+    assert!(super::validator("vsock:2:5000".to_string()).is_ok());
+    assert!(super::validator("vsock:2:x".to_string()).is_err());
+    assert!(super::validator("vsock:2:5000:1".to_string()).is_err());
+  }
+
   #[test]
   fn parse_net_args_ipv6_error1() {
     let entries = svec![":::"];
@@ -360,6 +360,7 @@ async fn compile_command(
   output: Option<PathBuf>,
   args: Vec<String>,
   target: Option<String>,
+  windows_options: tools::standalone::WindowsOptions,
 ) -> Result<(), AnyError> {
   let debug = flags.log_level == Some(log::Level::Debug);
 
@@ -400,6 +401,11 @@ async fn compile_command(
   // Select base binary based on target
   let original_binary =
     tools::standalone::get_base_binary(deno_dir, target.clone()).await?;
+  let original_binary = tools::standalone::apply_windows_options(
+    original_binary,
+    target.as_deref(),
+    windows_options,
+  )?;
 
   let final_bin = tools::standalone::create_standalone_binary(
     original_binary,
@@ -751,10 +757,21 @@ async fn doc_command(
   flags: Flags,
   source_file: Option<String>,
   json: bool,
+  markdown: bool,
+  out_dir: Option<PathBuf>,
   maybe_filter: Option<String>,
   private: bool,
 ) -> Result<(), AnyError> {
-  tools::doc::print_docs(flags, source_file, json, maybe_filter, private).await
+  tools::doc::print_docs(
+    flags,
+    source_file,
+    json,
+    markdown,
+    out_dir,
+    maybe_filter,
+    private,
+  )
+  .await
 }
 
 async fn format_command(
@@ -991,6 +1008,8 @@ async fn test_command(
   allow_none: bool,
   filter: Option<String>,
   shuffle: Option<u64>,
+  shard: Option<(usize, usize)>,
+  timeout: Option<u64>,
   concurrent_jobs: usize,
 ) -> Result<(), AnyError> {
   if let Some(ref coverage_dir) = flags.coverage_dir {
@@ -1208,6 +1227,8 @@ async fn test_command(
           true,
           filter.clone(),
           shuffle,
+          shard,
+          timeout,
           concurrent_jobs,
         )
         .await?;
@@ -1246,6 +1267,8 @@ async fn test_command(
       allow_none,
       filter,
       shuffle,
+      shard,
+      timeout,
       concurrent_jobs,
     )
     .await?;
@@ -1293,9 +1316,14 @@ fn get_subcommand(
     DenoSubcommand::Doc {
       source_file,
       json,
+      markdown,
+      out_dir,
       filter,
       private,
-    } => doc_command(flags, source_file, json, filter, private).boxed_local(),
+    } => {
+      doc_command(flags, source_file, json, markdown, out_dir, filter, private)
+        .boxed_local()
+    }
     DenoSubcommand::Eval { print, code, ext } => {
       eval_command(flags, code, ext, print).boxed_local()
     }
@@ -1307,8 +1335,19 @@ fn get_subcommand(
       output,
       args,
       target,
+      windows_icon,
+      windows_subsystem,
+      windows_version,
+      windows_metadata,
     } => {
-      compile_command(flags, source_file, output, args, target).boxed_local()
+      let windows_options = tools::standalone::WindowsOptions {
+        icon: windows_icon,
+        subsystem: windows_subsystem,
+        version: windows_version,
+        metadata: windows_metadata,
+      };
+      compile_command(flags, source_file, output, args, target, windows_options)
+        .boxed_local()
     }
     DenoSubcommand::Coverage {
       files,
@@ -1354,6 +1393,8 @@ fn get_subcommand(
       allow_none,
       filter,
       shuffle,
+      shard,
+      timeout,
       concurrent_jobs,
     } => test_command(
       flags,
@@ -1365,6 +1406,8 @@ fn get_subcommand(
       allow_none,
       filter,
       shuffle,
+      shard,
+      timeout,
       concurrent_jobs,
     )
     .boxed_local(),
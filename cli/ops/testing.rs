@@ -19,8 +19,14 @@ pub fn init(rt: &mut JsRuntime) {
   );
   super::reg_sync(rt, "op_get_test_origin", op_get_test_origin);
   super::reg_sync(rt, "op_dispatch_test_event", op_dispatch_test_event);
+  super::reg_sync(rt, "op_test_timed_out", op_test_timed_out);
 }
 
+/// Marker put into the op state once a test in the module has timed out. The
+/// timed out test may have left pending work behind, so the test runner
+/// doesn't wait for the event loop to drain afterwards.
+pub struct TestTimedOut;
+
 #[derive(Clone)]
 struct PermissionsHolder(Uuid, Permissions);
 
@@ -82,3 +88,12 @@ fn op_dispatch_test_event(
 
   Ok(())
 }
+
+fn op_test_timed_out(
+  state: &mut OpState,
+  _: (),
+  _: (),
+) -> Result<(), AnyError> {
+  state.put(TestTimedOut);
+  Ok(())
+}
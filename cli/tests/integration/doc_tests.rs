@@ -12,6 +12,11 @@ itest!(deno_doc {
   output: "deno_doc.out",
 });
 
+itest!(deno_doc_markdown {
+  args: "doc --output=markdown deno_doc.ts",
+  output: "deno_doc_markdown.out",
+});
+
 itest!(deno_doc_import_map {
   args: "doc --unstable --import-map=doc/import_map.json doc/use_import_map.js",
   output: "doc/use_import_map.out",
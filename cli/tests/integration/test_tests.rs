@@ -132,3 +132,21 @@ itest!(shuffle_with_seed {
   exit_code: 0,
   output: "test/shuffle.out",
 });
+
+itest!(shard_1_of_2 {
+  args: "test --shard=1/2 test/pass.ts",
+  exit_code: 0,
+  output: "test/shard_1_of_2.out",
+});
+
+itest!(shard_2_of_2 {
+  args: "test --shard=2/2 test/pass.ts",
+  exit_code: 0,
+  output: "test/shard_2_of_2.out",
+});
+
+itest!(timeout {
+  args: "test --timeout=200 --allow-net test/timeout.ts",
+  exit_code: 1,
+  output: "test/timeout.out",
+});
@@ -0,0 +1,20 @@
+Deno.test({
+  name: "fast",
+  timeout: 1000,
+  fn() {},
+});
+
+Deno.test({
+  name: "stuck on a listener",
+  timeout: 100,
+  async fn() {
+    const listener = Deno.listen({ port: 4321 });
+    await listener.accept();
+  },
+});
+
+Deno.test("stuck using the default", async function () {
+  await new Promise((resolve) => setTimeout(resolve, 60000));
+});
+
+Deno.test("after a test that left ops pending", function () {});
@@ -99,6 +99,20 @@ unitTest(async function blobStream(): Promise<void> {
   assertEquals(decoder.decode(bytes), "Hello World");
 });
 
+unitTest(async function blobStreamNestedParts(): Promise<void> {
+  const inner = new Blob(["b", new Blob(["c"])]);
+  // Nested blobs are walked part by part, not read through their own stream.
+  inner.stream = (): ReadableStream<Uint8Array> => {
+    throw new Error("nested Blob#stream() called");
+  };
+  const blob = new Blob(["a", inner, "d"]);
+  const chunks: string[] = [];
+  for await (const chunk of blob.stream()) {
+    chunks.push(new TextDecoder().decode(chunk));
+  }
+  assertEquals(chunks, ["a", "b", "c", "d"]);
+});
+
 unitTest(async function blobArrayBuffer(): Promise<void> {
   const uint = new Uint8Array([102, 111, 111]);
   const blob = new Blob([uint]);
@@ -596,6 +596,91 @@ unitTest(
   },
 );
 
+unitTest(
+  { perms: { net: true, read: true } },
+  async function fetchInitFormDataLazyFileBody(): Promise<void> {
+    const form = new FormData();
+    form.append("field", "value");
+    form.append("file", await Deno.openBlob("cli/tests/fixture.json"));
+    const response = await fetch("http://localhost:4545/echo_server", {
+      method: "POST",
+      body: form,
+    });
+    const resultForm = await response.formData();
+    assertEquals(resultForm.get("field"), "value");
+
+    const resultFile = resultForm.get("file") as File;
+    assertEquals(resultFile.name, "fixture.json");
+    assertEquals(
+      new Uint8Array(await resultFile.arrayBuffer()),
+      await Deno.readFile("cli/tests/fixture.json"),
+    );
+  },
+);
+
+unitTest(
+  { perms: { net: true } },
+  async function fetchUploadProgress(): Promise<void> {
+    const data = new Uint8Array(200000);
+    const progress: Deno.UploadProgress[] = [];
+    const response = await fetch("http://localhost:4545/echo_server", {
+      method: "POST",
+      body: data,
+      onUploadProgress(p: Deno.UploadProgress) {
+        progress.push(p);
+      },
+    });
+    assertEquals((await response.arrayBuffer()).byteLength, data.length);
+    assert(progress.length > 1);
+    for (let i = 1; i < progress.length; i++) {
+      assert(progress[i].loaded > progress[i - 1].loaded);
+    }
+    assertEquals(progress[progress.length - 1], {
+      loaded: data.length,
+      total: data.length,
+    });
+  },
+);
+
+unitTest(
+  { perms: { net: true } },
+  async function fetchUploadProgressStaticBody(): Promise<void> {
+    const data = new TextEncoder().encode("Hello World");
+    const progress: Deno.UploadProgress[] = [];
+    const response = await fetch("http://localhost:4545/echo_server", {
+      method: "POST",
+      body: data,
+      onUploadProgress(p: Deno.UploadProgress) {
+        progress.push(p);
+      },
+    });
+    assertEquals(await response.text(), "Hello World");
+    assertEquals(progress, [{ loaded: data.length, total: data.length }]);
+  },
+);
+
+unitTest(
+  { perms: { net: true } },
+  async function fetchUploadProgressThrows(): Promise<void> {
+    const data = new Uint8Array(200000);
+    let calls = 0;
+    await assertThrowsAsync(
+      () =>
+        fetch("http://localhost:4545/echo_server", {
+          method: "POST",
+          body: data,
+          onUploadProgress() {
+            calls++;
+            throw new RangeError("upload stopped");
+          },
+        }),
+      RangeError,
+      "upload stopped",
+    );
+    assertEquals(calls, 1);
+  },
+);
+
 unitTest({ perms: { net: true } }, async function fetchUserAgent(): Promise<
   void
 > {
@@ -1095,6 +1180,32 @@ unitTest(
   },
 );
 
+unitTest(
+  { perms: { net: true } },
+  async function fetchCustomClientResolve(): Promise<void> {
+    const client = Deno.createHttpClient({
+      resolve: { "deno-pinned.invalid": "127.0.0.1" },
+    });
+    const response = await fetch(
+      "http://deno-pinned.invalid:4545/cli/tests/fixture.json",
+      { client },
+    );
+    const json = await response.json();
+    assertEquals(json.name, "deno");
+    client.close();
+  },
+);
+
+unitTest(function createHttpClientSocksProxyBasicAuth(): void {
+  const client = Deno.createHttpClient({
+    proxy: {
+      url: "socks5h://127.0.0.1:9050",
+      basicAuth: { username: "user", password: "p@ss:word" },
+    },
+  });
+  client.close();
+});
+
 unitTest(
   {
     perms: { net: true },
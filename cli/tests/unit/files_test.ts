@@ -651,3 +651,38 @@ unitTest({ perms: { read: true } }, async function seekMode(): Promise<void> {
   assertEquals(new TextDecoder().decode(buf), "H");
   file.close();
 });
+
+unitTest(
+  { perms: { read: true, write: true } },
+  async function openBlobReadsLazily(): Promise<void> {
+    const tempDir = await Deno.makeTempDir();
+    const filename = tempDir + "/big.bin";
+    const data = new Uint8Array(200000);
+    for (let i = 0; i < data.length; i++) {
+      data[i] = i % 251;
+    }
+    await Deno.writeFile(filename, data);
+
+    const file = await Deno.openBlob(filename, { type: "application/foo" });
+    assert(file instanceof File);
+    assertEquals(file.name, "big.bin");
+    assertEquals(file.type, "application/foo");
+    assertEquals(file.size, data.length);
+    assertEquals(file.lastModified, Deno.statSync(filename).mtime!.getTime());
+
+    const slice = file.slice(70000, 140000);
+    assertEquals(
+      new Uint8Array(await slice.arrayBuffer()),
+      data.slice(70000, 140000),
+    );
+    assertEquals(new Uint8Array(await file.arrayBuffer()), data);
+
+    await Deno.remove(tempDir, { recursive: true });
+  },
+);
+
+unitTest(async function openBlobPerm(): Promise<void> {
+  await assertThrowsAsync(async () => {
+    await Deno.openBlob("cli/tests/fixture.json");
+  }, Deno.errors.PermissionDenied);
+});
@@ -98,3 +98,88 @@ unitTest(
     assertEquals(events, []);
   },
 );
+
+unitTest(
+  { perms: { read: true, write: true } },
+  async function watchFsIncludeExclude(): Promise<void> {
+    const testDir = await Deno.makeTempDir();
+    const iter = Deno.watchFs(testDir, {
+      recursive: true,
+      include: ["*.txt"],
+      exclude: ["skip*"],
+    });
+
+    const eventsPromise = getTwoEvents(iter);
+    Deno.writeFileSync(testDir + "/ignored.json", new Uint8Array([0]));
+    Deno.writeFileSync(testDir + "/skip.txt", new Uint8Array([0]));
+    Deno.writeFileSync(testDir + "/file1.txt", new Uint8Array([0]));
+    Deno.writeFileSync(testDir + "/file2.txt", new Uint8Array([0]));
+    Deno.writeFileSync(testDir + "/file3.txt", new Uint8Array([0]));
+
+    const events = await eventsPromise;
+    for (const event of events) {
+      for (const path of event.paths) {
+        assert(path.endsWith(".txt"));
+        assert(!path.endsWith("skip.txt"));
+      }
+    }
+    iter.close();
+  },
+);
+
+unitTest(
+  { perms: { read: true, write: true } },
+  async function watchFsExcludeRelativeToWatchedPath(): Promise<void> {
+    // The watched directory itself lives in a directory matching the
+    // exclusion, which must not hide its events.
+    const testDir = await Deno.makeTempDir();
+    const watchedDir = testDir + "/build/project";
+    await Deno.mkdir(watchedDir, { recursive: true });
+    const iter = Deno.watchFs(watchedDir, {
+      recursive: true,
+      exclude: ["build"],
+    });
+
+    const eventsPromise = getTwoEvents(iter);
+    Deno.mkdirSync(watchedDir + "/build");
+    Deno.writeFileSync(watchedDir + "/build/skipped.txt", new Uint8Array([0]));
+    Deno.writeFileSync(watchedDir + "/file1.txt", new Uint8Array([0]));
+    Deno.writeFileSync(watchedDir + "/file2.txt", new Uint8Array([0]));
+
+    const events = await eventsPromise;
+    for (const event of events) {
+      for (const path of event.paths) {
+        assert(!path.includes("/project/build"));
+      }
+    }
+    iter.close();
+  },
+);
+
+unitTest(
+  { perms: { read: true, write: true } },
+  async function watchFsDebounce(): Promise<void> {
+    const testDir = await Deno.makeTempDir();
+    const iter = Deno.watchFs(testDir, { recursive: true, debounce: 200 });
+
+    const eventPromise = iter[Symbol.asyncIterator]().next();
+    Deno.mkdirSync(testDir + "/dir1");
+    Deno.mkdirSync(testDir + "/dir2");
+
+    // Both creations arrive within the debounce period and nothing happens in
+    // between, so they are merged into a single event.
+    const { value: event } = await eventPromise;
+    assertEquals(event!.kind, "create");
+    assertEquals(event!.paths.length, 2);
+    iter.close();
+  },
+);
+
+unitTest(
+  { perms: { read: true, write: true } },
+  function watchFsInvalidGlob() {
+    assertThrows(() => {
+      Deno.watchFs(".", { recursive: true, include: ["{a,b"] });
+    }, TypeError);
+  },
+);
@@ -19,6 +19,48 @@ unitTest({ perms: { net: true } }, function netTcpListenClose(): void {
   listener.close();
 });
 
+unitTest(
+  { ignore: Deno.build.os === "windows", perms: { net: true } },
+  async function netTcpListenReusePort(): Promise<void> {
+    const port = 3500;
+    const listener1 = Deno.listen({ port, reusePort: true });
+    const listener2 = Deno.listen({ port, reusePort: true });
+    assertThrows(() => {
+      Deno.listen({ port });
+    }, Deno.errors.AddrInUse);
+
+    // Either listener may be handed the connection.
+    const accepts = [listener1.accept(), listener2.accept()];
+    const conn = await Deno.connect({ port });
+    (await Promise.race(accepts)).close();
+    conn.close();
+
+    listener1.close();
+    listener2.close();
+    await Promise.allSettled(accepts);
+  },
+);
+
+unitTest(
+  { perms: { net: true } },
+  function netUdpListenReusePort(): void {
+    assertThrows(() => {
+      Deno.listenDatagram({ port: 3500, transport: "udp", reusePort: true });
+    }, TypeError);
+  },
+);
+
+unitTest(
+  { ignore: Deno.build.os === "windows", perms: { read: true, write: true } },
+  function netUnixListenReusePort(): void {
+    const filePath = Deno.makeTempFileSync();
+    const options = { path: filePath, transport: "unix" as const };
+    assertThrows(() => {
+      Deno.listen({ ...options, reusePort: true } as typeof options);
+    }, TypeError);
+  },
+);
+
 unitTest(
   {
     perms: { net: true },
@@ -96,6 +138,47 @@ unitTest(
   },
 );
 
+unitTest(
+  { ignore: Deno.build.os !== "linux", perms: { net: false } },
+  function netVsockListenNetPermission(): void {
+    assertThrows(() => {
+      Deno.listen({ port: 4500, transport: "vsock" });
+    }, Deno.errors.PermissionDenied);
+  },
+);
+
+unitTest(
+  { ignore: Deno.build.os !== "linux", perms: { net: true, read: true } },
+  async function netVsockDialListen(): Promise<void> {
+    // Connecting to the local host needs the vsock_loopback kernel module.
+    try {
+      Deno.statSync("/sys/module/vsock_loopback");
+    } catch {
+      return;
+    }
+    const listener = Deno.listen({ port: 4500, transport: "vsock" });
+    listener.accept().then(
+      async (conn): Promise<void> => {
+        await conn.write(new Uint8Array([1, 2, 3]));
+        conn.close();
+      },
+    );
+    // Context ID 1 is the local host.
+    const conn = await Deno.connect({ cid: 1, port: 4500, transport: "vsock" });
+    assert(conn.remoteAddr.transport === "vsock");
+    assertEquals(conn.remoteAddr.cid, 1);
+    assertEquals(conn.remoteAddr.port, 4500);
+    const buf = new Uint8Array(1024);
+    const readResult = await conn.read(buf);
+    assertEquals(readResult, 3);
+    assertEquals(buf.subarray(0, 3), new Uint8Array([1, 2, 3]));
+    assertEquals(await conn.read(buf), null);
+
+    listener.close();
+    conn.close();
+  },
+);
+
 unitTest(
   {
     perms: { net: true },
@@ -285,6 +368,38 @@ unitTest(
   },
 );
 
+unitTest(
+  { perms: { net: true } },
+  async function netUdpSendReceiveBatch(): Promise<void> {
+    const alice = Deno.listenDatagram({ port: 3500, transport: "udp" });
+    const bob = Deno.listenDatagram({ port: 4501, transport: "udp" });
+
+    // Equal sized messages to the same peer can be sent as one segmented
+    // datagram, so this makes two such runs.
+    const messages: [Uint8Array, Deno.Addr][] = [
+      [new Uint8Array([1, 2, 3]), bob.addr],
+      [new Uint8Array([4, 5, 6]), bob.addr],
+      [new Uint8Array([7, 8, 9]), bob.addr],
+      [new Uint8Array([1, 2, 3, 4, 5]), bob.addr],
+      [new Uint8Array([6, 7, 8, 9, 10]), bob.addr],
+    ];
+    assertEquals(await alice.sendBatch(messages), 5);
+
+    const received: [Uint8Array, Deno.Addr][] = [];
+    while (received.length < 5) {
+      received.push(...await bob.receiveBatch(8, 16));
+    }
+    assertEquals(received.length, 5);
+    for (const [i, [recvd, remote]] of received.entries()) {
+      assert(remote.transport === "udp");
+      assertEquals(remote.port, 3500);
+      assertEquals(recvd, messages[i][0]);
+    }
+    alice.close();
+    bob.close();
+  },
+);
+
 unitTest(
   { perms: { net: true } },
   async function netUdpConcurrentSendReceive(): Promise<void> {
@@ -598,3 +713,96 @@ unitTest(
     listener.close();
   },
 );
+
+unitTest(
+  {
+    ignore: Deno.build.os === "windows",
+    perms: { read: true, write: true, net: true },
+  },
+  async function netUnixSendReceiveResources(): Promise<void> {
+    const filePath = await Deno.makeTempFile();
+    const unixListener = Deno.listen({ path: filePath, transport: "unix" });
+    const accepted = unixListener.accept();
+    const client = await Deno.connect({ path: filePath, transport: "unix" });
+    const server = await accepted;
+
+    const listener = Deno.listen({ hostname: "127.0.0.1", port: 3500 });
+    const data = new Uint8Array([42]);
+    assertEquals(await Deno.sendResources(client, [listener], data), 1);
+    listener.close();
+
+    const buf = new Uint8Array(8);
+    const { nread, resources } = await Deno.receiveResources(server, buf);
+    assertEquals(nread, 1);
+    assertEquals(buf[0], 42);
+    assertEquals(resources.length, 1);
+    const received = resources[0] as Deno.Listener;
+    assertEquals(received.addr, {
+      transport: "tcp",
+      hostname: "127.0.0.1",
+      port: 3500,
+    });
+
+    // The listener still accepts connections after being passed around.
+    const connecting = Deno.connect({ hostname: "127.0.0.1", port: 3500 });
+    const conn = await received.accept();
+    (await connecting).close();
+    conn.close();
+
+    received.close();
+    client.close();
+    server.close();
+    unixListener.close();
+  },
+);
+
+unitTest(
+  {
+    ignore: Deno.build.os === "windows",
+    perms: { read: true, write: true },
+  },
+  async function netUnixReceiveResourcesTruncated(): Promise<void> {
+    const filePath = await Deno.makeTempFile();
+    const unixListener = Deno.listen({ path: filePath, transport: "unix" });
+    const accepted = unixListener.accept();
+    const client = await Deno.connect({ path: filePath, transport: "unix" });
+    const server = await accepted;
+
+    const file = await Deno.open("cli/tests/fixture.json");
+    await Deno.sendResources(client, new Array(17).fill(file));
+    file.close();
+
+    const { resources, truncated } = await Deno.receiveResources(server);
+    assertEquals(resources.length, 16);
+    assert(truncated);
+
+    for (const resource of resources) {
+      resource.close();
+    }
+    client.close();
+    server.close();
+    unixListener.close();
+  },
+);
+
+unitTest(
+  {
+    ignore: Deno.build.os === "windows",
+    perms: { read: true, write: true },
+  },
+  async function netUnixSendResourcesEmptyData(): Promise<void> {
+    const filePath = await Deno.makeTempFile();
+    const unixListener = Deno.listen({ path: filePath, transport: "unix" });
+    const accepted = unixListener.accept();
+    const client = await Deno.connect({ path: filePath, transport: "unix" });
+    const server = await accepted;
+
+    await assertThrowsAsync(async () => {
+      await Deno.sendResources(client, [], new Uint8Array());
+    }, TypeError);
+
+    client.close();
+    server.close();
+    unixListener.close();
+  },
+);
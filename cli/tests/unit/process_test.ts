@@ -510,3 +510,82 @@ unitTest({ perms: { run: true, read: true } }, function killFailed(): void {
 
   p.close();
 });
+
+unitTest(
+  {
+    // No pseudo-terminals on windows.
+    ignore: Deno.build.os === "windows",
+    perms: { run: true },
+  },
+  async function runWithPty(): Promise<void> {
+    const p = Deno.run({
+      cmd: ["sh", "-c", "test -t 0 && echo tty; read x; stty size; echo $x"],
+      pty: { rows: 24, cols: 80 },
+    });
+    assert(p.pty);
+    assert(!p.stdin);
+    assert(!p.stdout);
+
+    p.pty.resize({ rows: 30, cols: 100 });
+    await p.pty.write(new TextEncoder().encode("hello\n"));
+    const output = new TextDecoder().decode(await Deno.readAll(p.pty));
+    assertStringIncludes(output, "tty");
+    assertStringIncludes(output, "30 100");
+    assertStringIncludes(output, "hello");
+
+    const status = await p.status();
+    assertEquals(status.success, true);
+    p.pty.close();
+    p.close();
+  },
+);
+
+unitTest({ perms: { run: true } }, function runWithPtyAndStdio(): void {
+  assertThrows(() => {
+    Deno.run({ cmd: ["sh"], pty: true, stdout: "piped" });
+  }, TypeError);
+});
+
+unitTest(
+  {
+    ignore: Deno.build.os === "windows",
+    perms: { run: true },
+  },
+  async function killTreeSuccess(): Promise<void> {
+    const p = Deno.run({
+      cmd: ["sh", "-c", "sleep 100 & echo started; wait"],
+      stdout: "piped",
+      processGroup: true,
+    });
+    const buf = new Uint8Array(8);
+    await p.stdout!.read(buf);
+
+    p.killTree(Deno.Signal.SIGKILL);
+    const status = await p.status();
+    assertEquals(status.success, false);
+    assertEquals(status.signal, Deno.Signal.SIGKILL);
+
+    // The backgrounded `sleep` holds the write end of stdout; it only reaches
+    // EOF if the grandchild was killed too.
+    const output = await Deno.readAll(p.stdout!);
+    assertEquals(output.length, 0);
+    p.stdout!.close();
+    p.close();
+  },
+);
+
+unitTest(
+  { perms: { run: true, read: true } },
+  function killTreeWithoutProcessGroup(): void {
+    const p = Deno.run({
+      cmd: [Deno.execPath(), "eval", "setTimeout(() => {}, 10000)"],
+    });
+
+    assertThrows(() => {
+      p.killTree(Deno.Signal.SIGKILL);
+    }, TypeError);
+
+    p.kill(Deno.Signal.SIGKILL);
+    p.close();
+  },
+);
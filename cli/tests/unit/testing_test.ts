@@ -25,3 +25,15 @@ unitTest(function nameOfTestCaseCantBeEmpty(): void {
     "The test name can't be empty",
   );
 });
+
+unitTest(function testTimeoutMustBeInRange(): void {
+  for (const timeout of [0, -1, NaN, Infinity, 2 ** 31]) {
+    assertThrows(
+      () => {
+        Deno.test({ name: "out of range timeout", timeout, fn: () => {} });
+      },
+      TypeError,
+      "The test timeout must be a positive number",
+    );
+  }
+});
@@ -205,3 +205,68 @@ unitTest({
   const resources = Object.keys(Deno.resources());
   Deno.close(Number(resources[resources.length - 1]));
 });
+
+// Skip this test on linux CI, because the vulkan emulator is not good enough
+// yet, and skip on macOS because these do not have virtual GPUs.
+unitTest({
+  perms: { read: true, env: true },
+  ignore: (Deno.build.os === "linux" || Deno.build.os === "darwin") && isCI,
+}, async function webgpuTimestampQuery() {
+  const adapter = await navigator.gpu.requestAdapter();
+  assert(adapter);
+
+  if (!adapter.features.has("timestamp-query")) {
+    // TODO(lucacasonato): webgpu spec should add a explicit destroy method for
+    // adapters.
+    const resources = Object.keys(Deno.resources());
+    Deno.close(Number(resources[resources.length - 1]));
+    return;
+  }
+
+  const device = await adapter.requestDevice({
+    nonGuaranteedFeatures: ["timestamp-query"],
+  });
+  assert(device);
+
+  const querySet = device.createQuerySet({
+    type: "timestamp",
+    count: 2,
+  });
+
+  const size = 2 * BigUint64Array.BYTES_PER_ELEMENT;
+
+  const resolveBuffer = device.createBuffer({
+    size,
+    usage: 0x200 | 4,
+  });
+
+  const stagingBuffer = device.createBuffer({
+    size,
+    usage: 1 | 8,
+  });
+
+  const encoder = device.createCommandEncoder();
+  encoder.writeTimestamp(querySet, 0);
+  const computePass = encoder.beginComputePass();
+  computePass.endPass();
+  encoder.writeTimestamp(querySet, 1);
+  encoder.resolveQuerySet(querySet, 0, 2, resolveBuffer, 0);
+  encoder.copyBufferToBuffer(resolveBuffer, 0, stagingBuffer, 0, size);
+
+  device.queue.submit([encoder.finish()]);
+
+  await stagingBuffer.mapAsync(1);
+
+  const [start, end] = new BigUint64Array(stagingBuffer.getMappedRange());
+  assert(end >= start);
+
+  stagingBuffer.unmap();
+
+  querySet.destroy();
+  device.destroy();
+
+  // TODO(lucacasonato): webgpu spec should add a explicit destroy method for
+  // adapters.
+  const resources = Object.keys(Deno.resources());
+  Deno.close(Number(resources[resources.length - 1]));
+});
@@ -0,0 +1,6 @@
+onmessage = async () => {
+  const { state } = await Deno.permissions.query({
+    name: "net",
+  });
+  postMessage(state);
+};
@@ -850,3 +850,31 @@ Deno.test({
     worker.terminate();
   },
 });
+
+Deno.test({
+  name: "Worker permissions are revoked along with parent permissions",
+  fn: async function () {
+    const worker = new Worker(
+      new URL("./net_check_worker.js", import.meta.url).href,
+      {
+        type: "module",
+        deno: {
+          namespace: true,
+          permissions: "inherit",
+        },
+      },
+    );
+
+    let promise = deferred();
+    worker.onmessage = ({ data }) => promise.resolve(data);
+    worker.postMessage(null);
+    assertEquals(await promise, "granted");
+
+    await Deno.permissions.revoke({ name: "net" });
+
+    promise = deferred();
+    worker.postMessage(null);
+    assertEquals(await promise, "prompt");
+    worker.terminate();
+  },
+});
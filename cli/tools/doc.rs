@@ -9,6 +9,7 @@ use crate::media_type::MediaType;
 use crate::module_graph;
 use crate::program_state::ProgramState;
 use crate::specifier_handler::FetchHandler;
+use crate::tools::doc_markdown;
 use crate::write_json_to_stdout;
 use crate::write_to_stdout_ignore_sigpipe;
 use deno_core::error::AnyError;
@@ -19,6 +20,7 @@ use deno_core::resolve_url_or_path;
 use deno_doc as doc;
 use deno_doc::parser::DocFileLoader;
 use deno_runtime::permissions::Permissions;
+use log::info;
 use std::path::PathBuf;
 use std::pin::Pin;
 use std::sync::Arc;
@@ -30,7 +32,7 @@ type DocResult = Result<(Syntax, String), doc::DocError>;
 /// which never even references the loader, so this is just a stub for that scenario.
 ///
 /// TODO(Liamolucko): Refactor `deno_doc` so this isn't necessary.
-struct StubDocLoader;
+pub(crate) struct StubDocLoader;
 
 impl DocFileLoader for StubDocLoader {
   fn resolve(
@@ -81,11 +83,18 @@ pub async fn print_docs(
   flags: Flags,
   source_file: Option<String>,
   json: bool,
+  markdown: bool,
+  out_dir: Option<PathBuf>,
   maybe_filter: Option<String>,
   private: bool,
 ) -> Result<(), AnyError> {
   let program_state = ProgramState::build(flags.clone()).await?;
   let source_file = source_file.unwrap_or_else(|| "--builtin".to_string());
+  let title = if source_file == "--builtin" {
+    "Deno".to_string()
+  } else {
+    source_file.clone()
+  };
 
   let parse_result = if source_file == "--builtin" {
     let loader = Box::new(StubDocLoader);
@@ -146,6 +155,30 @@ pub async fn print_docs(
 
   if json {
     write_json_to_stdout(&doc_nodes)
+  } else if markdown {
+    doc_nodes.retain(|doc_node| doc_node.kind != doc::DocNodeKind::Import);
+    if let Some(filter) = maybe_filter {
+      doc_nodes =
+        doc::find_nodes_by_name_recursively(doc_nodes, filter.clone());
+      if doc_nodes.is_empty() {
+        eprintln!("Node {} was not found!", filter);
+        std::process::exit(1);
+      }
+    }
+    if let Some(out_dir) = out_dir {
+      std::fs::create_dir_all(&out_dir)?;
+      for (file_name, content) in doc_markdown::render_files(&title, &doc_nodes)
+      {
+        let path = out_dir.join(file_name);
+        std::fs::write(&path, content)?;
+        info!("{} {}", colors::green("Write"), path.display());
+      }
+      Ok(())
+    } else {
+      let document = doc_markdown::render_document(&title, &doc_nodes);
+      write_to_stdout_ignore_sigpipe(document.as_bytes())
+        .map_err(AnyError::from)
+    }
   } else {
     doc_nodes.retain(|doc_node| doc_node.kind != doc::DocNodeKind::Import);
     let details = if let Some(filter) = maybe_filter {
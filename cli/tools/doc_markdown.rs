@@ -0,0 +1,913 @@
+// Copyright 2018-2021 the Deno authors. All rights reserved. MIT license.
+
+//! Renders `deno_doc` nodes as Markdown.
+//!
+//! Documentation is either rendered as a single document, in which case
+//! symbols are linked to with in-page anchors, or split into an `index.md`
+//! plus one file per top-level symbol.
+
+use deno_doc::params::ParamDef;
+use deno_doc::ts_type::TsTypeDef;
+use deno_doc::ts_type_param::TsTypeParamDef;
+use deno_doc::DocNode;
+use deno_doc::DocNodeKind;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt::Write;
+use swc_ecmascript::ast::Accessibility;
+use swc_ecmascript::ast::MethodKind;
+use swc_ecmascript::ast::VarDeclKind;
+
+const INDEX_FILE: &str = "index.md";
+
+/// Top-level symbol kinds in the order they are listed, with section titles.
+const SECTIONS: &[(DocNodeKind, &str)] = &[
+  (DocNodeKind::Namespace, "Namespaces"),
+  (DocNodeKind::Class, "Classes"),
+  (DocNodeKind::Enum, "Enums"),
+  (DocNodeKind::Variable, "Variables"),
+  (DocNodeKind::Function, "Functions"),
+  (DocNodeKind::Interface, "Interfaces"),
+  (DocNodeKind::TypeAlias, "Type Aliases"),
+];
+
+/// Renders `doc_nodes` into a single Markdown document titled `title`.
+pub fn render_document(title: &str, doc_nodes: &[DocNode]) -> String {
+  let symbols = group_symbols(doc_nodes);
+  let mut renderer = Renderer::new(false);
+  renderer.collect_links(&symbols, "");
+
+  let mut out = format!("# {}\n", escape(title));
+  for (kind, section) in SECTIONS {
+    let symbols: Vec<&Symbol> =
+      symbols.iter().filter(|s| s.kind() == kind).collect();
+    if symbols.is_empty() {
+      continue;
+    }
+    writeln!(out, "\n## {}", section).unwrap();
+    for symbol in symbols {
+      renderer.render_symbol(&mut out, symbol, "", 3);
+    }
+  }
+  out
+}
+
+/// Renders `doc_nodes` into an `index.md` and one file per top-level symbol,
+/// returned as `(file name, contents)` pairs.
+pub fn render_files(
+  title: &str,
+  doc_nodes: &[DocNode],
+) -> Vec<(String, String)> {
+  let symbols = group_symbols(doc_nodes);
+  let mut renderer = Renderer::new(true);
+  renderer.collect_links(&symbols, "");
+
+  let mut index = format!("# {}\n", escape(title));
+  let mut files = Vec::new();
+  for (kind, section) in SECTIONS {
+    let symbols: Vec<&Symbol> =
+      symbols.iter().filter(|s| s.kind() == kind).collect();
+    if symbols.is_empty() {
+      continue;
+    }
+    writeln!(index, "\n## {}\n", section).unwrap();
+    for symbol in symbols {
+      let file_name = renderer.files[&symbol.name].clone();
+      write!(index, "- [{}]({})", escape(&symbol.name), file_name).unwrap();
+      if let Some(summary) = symbol.js_doc().and_then(first_paragraph) {
+        write!(index, ": {}", summary).unwrap();
+      }
+      index.push('\n');
+
+      let mut out = format!("[{}]({})\n", escape(title), INDEX_FILE);
+      renderer.render_symbol(&mut out, symbol, "", 1);
+      files.push((file_name, out));
+    }
+  }
+  files.insert(0, (INDEX_FILE.to_string(), index));
+  files
+}
+
+/// All declarations of one name, e.g. function overloads or a class merged
+/// with a namespace.
+struct Symbol<'a> {
+  name: String,
+  nodes: Vec<&'a DocNode>,
+}
+
+impl<'a> Symbol<'a> {
+  fn kind(&self) -> &DocNodeKind {
+    &self.nodes[0].kind
+  }
+
+  fn js_doc(&self) -> Option<&str> {
+    self.nodes.iter().find_map(|node| js_doc(&node.js_doc))
+  }
+
+  fn elements(&self) -> impl Iterator<Item = &'a [DocNode]> + '_ {
+    self.nodes.iter().copied().filter_map(|node| {
+      node
+        .namespace_def
+        .as_ref()
+        .map(|def| def.elements.as_slice())
+    })
+  }
+}
+
+fn group_symbols(doc_nodes: &[DocNode]) -> Vec<Symbol<'_>> {
+  let mut symbols: Vec<Symbol> = Vec::new();
+  for node in doc_nodes {
+    if node.kind == DocNodeKind::Import {
+      continue;
+    }
+    match symbols.iter_mut().find(|s| s.name == node.name) {
+      Some(symbol) => symbol.nodes.push(node),
+      None => symbols.push(Symbol {
+        name: node.name.clone(),
+        nodes: vec![node],
+      }),
+    }
+  }
+  symbols.sort_by(|a, b| a.name.cmp(&b.name));
+  symbols
+}
+
+/// Returns `name`, or `name-2`, `name-3`, ... if a name that only differs
+/// from it in case has already been taken, since file systems and anchors
+/// may not tell those apart. Identifiers can't contain `-`, so a suffixed
+/// name never clashes with another symbol.
+fn unique_name(name: &str, taken: &mut HashSet<String>) -> String {
+  let mut candidate = name.to_string();
+  let mut n = 1;
+  while !taken.insert(candidate.to_lowercase()) {
+    n += 1;
+    candidate = format!("{}-{}", name, n);
+  }
+  candidate
+}
+
+struct Renderer {
+  multi_file: bool,
+  /// Qualified symbol names to link targets.
+  links: HashMap<String, String>,
+  /// Qualified symbol names to their anchors.
+  anchors: HashMap<String, String>,
+  /// Top-level symbol names to the files they are rendered into.
+  files: HashMap<String, String>,
+  taken_anchors: HashSet<String>,
+  taken_files: HashSet<String>,
+}
+
+impl Renderer {
+  fn new(multi_file: bool) -> Self {
+    let mut taken_files = HashSet::new();
+    taken_files.insert("index".to_string());
+    Self {
+      multi_file,
+      links: HashMap::new(),
+      anchors: HashMap::new(),
+      files: HashMap::new(),
+      taken_anchors: HashSet::new(),
+      taken_files,
+    }
+  }
+
+  fn collect_links(&mut self, symbols: &[Symbol], namespace: &str) {
+    for symbol in symbols {
+      let qualified_name = qualify(namespace, &symbol.name);
+      let anchor =
+        unique_name(&qualified_name.to_lowercase(), &mut self.taken_anchors);
+      if namespace.is_empty() {
+        let file_name =
+          format!("{}.md", unique_name(&symbol.name, &mut self.taken_files));
+        self.files.insert(symbol.name.clone(), file_name);
+      }
+      let target = if !self.multi_file {
+        format!("#{}", anchor)
+      } else if namespace.is_empty() {
+        self.files[&symbol.name].clone()
+      } else {
+        let root = namespace.split('.').next().unwrap();
+        format!("{}#{}", self.files[root], anchor)
+      };
+      self.links.insert(qualified_name.clone(), target);
+      self.anchors.insert(qualified_name.clone(), anchor);
+
+      for elements in symbol.elements() {
+        self.collect_links(&group_symbols(elements), &qualified_name);
+      }
+    }
+  }
+
+  fn link(&self, name: &str, namespace: &str) -> String {
+    let mut scope = namespace;
+    loop {
+      if let Some(target) = self.links.get(&qualify(scope, name)) {
+        return format!("[{}]({})", escape(name), target);
+      }
+      if scope.is_empty() {
+        return escape(name);
+      }
+      scope = scope.rfind('.').map_or("", |i| &scope[..i]);
+    }
+  }
+
+  fn render_symbol(
+    &self,
+    out: &mut String,
+    symbol: &Symbol,
+    namespace: &str,
+    level: usize,
+  ) {
+    let qualified_name = qualify(namespace, &symbol.name);
+    write!(
+      out,
+      "\n<a name=\"{}\"></a>\n\n{} {}\n",
+      self.anchors[&qualified_name],
+      "#".repeat(level),
+      escape(&qualified_name)
+    )
+    .unwrap();
+
+    for node in &symbol.nodes {
+      out.push('\n');
+      out.push_str(&self.signature(node, namespace));
+      out.push('\n');
+      if let Some(doc) = js_doc(&node.js_doc) {
+        writeln!(out, "\n{}", doc.trim()).unwrap();
+      }
+      match node.kind {
+        DocNodeKind::Class => self.render_class(out, node, namespace),
+        DocNodeKind::Interface => self.render_interface(out, node, namespace),
+        DocNodeKind::Enum => render_enum(out, node),
+        _ => {}
+      }
+    }
+
+    for elements in symbol.elements() {
+      for element in group_symbols(elements) {
+        self.render_symbol(out, &element, &qualified_name, (level + 1).min(6));
+      }
+    }
+  }
+
+  fn signature(&self, node: &DocNode, namespace: &str) -> String {
+    let name = escape(&node.name);
+    if let Some(def) = &node.function_def {
+      let mut prefix = String::new();
+      if def.is_async {
+        prefix.push_str("async ");
+      }
+      prefix.push_str("function");
+      if def.is_generator {
+        prefix.push_str("\\*");
+      }
+      format!(
+        "**{}** {}{}",
+        prefix,
+        name,
+        self.fn_signature(
+          &def.type_params,
+          &def.params,
+          def.return_type.as_ref(),
+          namespace
+        )
+      )
+    } else if let Some(def) = &node.variable_def {
+      let kind = match def.kind {
+        VarDeclKind::Var => "var",
+        VarDeclKind::Let => "let",
+        VarDeclKind::Const => "const",
+      };
+      let ts_type = self.type_annotation(def.ts_type.as_ref(), namespace);
+      format!("**{}** {}{}", kind, name, ts_type)
+    } else if let Some(def) = &node.class_def {
+      let mut out = String::new();
+      if def.is_abstract {
+        out.push_str("**abstract** ");
+      }
+      write!(
+        out,
+        "**class** {}{}",
+        name,
+        self.type_params(&def.type_params, namespace)
+      )
+      .unwrap();
+      if let Some(extends) = &def.extends {
+        write!(out, " **extends** {}", self.link(extends, namespace)).unwrap();
+        out.push_str(&self.type_args(&def.super_type_params, namespace));
+      }
+      let implements = self.type_list(&def.implements, namespace);
+      if !implements.is_empty() {
+        write!(out, " **implements** {}", implements).unwrap();
+      }
+      out
+    } else if let Some(def) = &node.interface_def {
+      let mut out = format!(
+        "**interface** {}{}",
+        name,
+        self.type_params(&def.type_params, namespace)
+      );
+      let extends = self.type_list(&def.extends, namespace);
+      if !extends.is_empty() {
+        write!(out, " **extends** {}", extends).unwrap();
+      }
+      out
+    } else if let Some(def) = &node.type_alias_def {
+      format!(
+        "**type** {}{} = {}",
+        name,
+        self.type_params(&def.type_params, namespace),
+        self.ts_type(&def.ts_type, namespace)
+      )
+    } else if node.enum_def.is_some() {
+      format!("**enum** {}", name)
+    } else if node.namespace_def.is_some() {
+      format!("**namespace** {}", name)
+    } else {
+      name
+    }
+  }
+
+  fn render_class(&self, out: &mut String, node: &DocNode, namespace: &str) {
+    let def = match &node.class_def {
+      Some(def) => def,
+      None => return,
+    };
+
+    if !def.constructors.is_empty() {
+      out.push_str("\n**Constructors**\n\n");
+      for ctor in &def.constructors {
+        let item = format!(
+          "{}**new** {}({})",
+          accessibility(&ctor.accessibility),
+          escape(&node.name),
+          self.params(&ctor.params, namespace)
+        );
+        push_item(out, &item, js_doc(&ctor.js_doc));
+      }
+    }
+
+    if !def.properties.is_empty() || !def.index_signatures.is_empty() {
+      out.push_str("\n**Properties**\n\n");
+      for signature in &def.index_signatures {
+        let item = self.index_signature(
+          signature.readonly,
+          &signature.params,
+          signature.ts_type.as_ref(),
+          namespace,
+        );
+        push_item(out, &item, None);
+      }
+      for prop in &def.properties {
+        let mut item = accessibility(&prop.accessibility).to_string();
+        item.push_str(&modifiers(prop.is_static, prop.is_abstract));
+        if prop.readonly {
+          item.push_str("**readonly** ");
+        }
+        item.push_str(&member_name(&prop.name, false, prop.optional));
+        item.push_str(&self.type_annotation(prop.ts_type.as_ref(), namespace));
+        push_item(out, &item, js_doc(&prop.js_doc));
+      }
+    }
+
+    if !def.methods.is_empty() {
+      out.push_str("\n**Methods**\n\n");
+      for method in &def.methods {
+        let function_def = &method.function_def;
+        let mut item = accessibility(&method.accessibility).to_string();
+        item.push_str(&modifiers(method.is_static, method.is_abstract));
+        match method.kind {
+          MethodKind::Getter => item.push_str("**get** "),
+          MethodKind::Setter => item.push_str("**set** "),
+          MethodKind::Method => {}
+        }
+        if function_def.is_async {
+          item.push_str("**async** ");
+        }
+        if function_def.is_generator {
+          item.push_str("\\*");
+        }
+        item.push_str(&member_name(&method.name, false, method.optional));
+        item.push_str(&self.fn_signature(
+          &function_def.type_params,
+          &function_def.params,
+          function_def.return_type.as_ref(),
+          namespace,
+        ));
+        push_item(out, &item, js_doc(&method.js_doc));
+      }
+    }
+  }
+
+  fn render_interface(
+    &self,
+    out: &mut String,
+    node: &DocNode,
+    namespace: &str,
+  ) {
+    let def = match &node.interface_def {
+      Some(def) => def,
+      None => return,
+    };
+
+    if !def.call_signatures.is_empty() {
+      out.push_str("\n**Call Signatures**\n\n");
+      for signature in &def.call_signatures {
+        let item = self.fn_signature(
+          &signature.type_params,
+          &signature.params,
+          signature.ts_type.as_ref(),
+          namespace,
+        );
+        push_item(out, &item, js_doc(&signature.js_doc));
+      }
+    }
+
+    if !def.properties.is_empty() || !def.index_signatures.is_empty() {
+      out.push_str("\n**Properties**\n\n");
+      for signature in &def.index_signatures {
+        let item = self.index_signature(
+          signature.readonly,
+          &signature.params,
+          signature.ts_type.as_ref(),
+          namespace,
+        );
+        push_item(out, &item, None);
+      }
+      for prop in &def.properties {
+        let item = format!(
+          "{}{}",
+          member_name(&prop.name, prop.computed, prop.optional),
+          self.type_annotation(prop.ts_type.as_ref(), namespace)
+        );
+        push_item(out, &item, js_doc(&prop.js_doc));
+      }
+    }
+
+    if !def.methods.is_empty() {
+      out.push_str("\n**Methods**\n\n");
+      for method in &def.methods {
+        let item = format!(
+          "{}{}",
+          member_name(&method.name, false, method.optional),
+          self.fn_signature(
+            &method.type_params,
+            &method.params,
+            method.return_type.as_ref(),
+            namespace
+          )
+        );
+        push_item(out, &item, js_doc(&method.js_doc));
+      }
+    }
+  }
+
+  /// Renders `<T>(params): ReturnType` for a function-like definition.
+  fn fn_signature(
+    &self,
+    type_params: &[TsTypeParamDef],
+    params: &[ParamDef],
+    return_type: Option<&TsTypeDef>,
+    namespace: &str,
+  ) -> String {
+    format!(
+      "{}({}){}",
+      self.type_params(type_params, namespace),
+      self.params(params, namespace),
+      self.type_annotation(return_type, namespace)
+    )
+  }
+
+  fn index_signature(
+    &self,
+    readonly: bool,
+    params: &[ParamDef],
+    ts_type: Option<&TsTypeDef>,
+    namespace: &str,
+  ) -> String {
+    format!(
+      "{}\\[{}\\]{}",
+      if readonly { "**readonly** " } else { "" },
+      self.params(params, namespace),
+      self.type_annotation(ts_type, namespace)
+    )
+  }
+
+  fn type_annotation(
+    &self,
+    ts_type: Option<&TsTypeDef>,
+    namespace: &str,
+  ) -> String {
+    match ts_type {
+      Some(ts_type) => format!(": {}", self.ts_type(ts_type, namespace)),
+      None => String::new(),
+    }
+  }
+
+  fn type_params(&self, params: &[TsTypeParamDef], namespace: &str) -> String {
+    if params.is_empty() {
+      return String::new();
+    }
+    let params: Vec<String> = params
+      .iter()
+      .map(|param| {
+        let mut out = escape(&param.name);
+        if let Some(constraint) = &param.constraint {
+          write!(out, " **extends** {}", self.ts_type(constraint, namespace))
+            .unwrap();
+        }
+        if let Some(default) = &param.default {
+          write!(out, " = {}", self.ts_type(default, namespace)).unwrap();
+        }
+        out
+      })
+      .collect();
+    format!("\\<{}\\>", params.join(", "))
+  }
+
+  fn type_args(&self, args: &[TsTypeDef], namespace: &str) -> String {
+    if args.is_empty() {
+      String::new()
+    } else {
+      format!("\\<{}\\>", self.type_list(args, namespace))
+    }
+  }
+
+  fn type_list(&self, types: &[TsTypeDef], namespace: &str) -> String {
+    self.join_types(types, ", ", namespace)
+  }
+
+  fn join_types(
+    &self,
+    types: &[TsTypeDef],
+    separator: &str,
+    namespace: &str,
+  ) -> String {
+    types
+      .iter()
+      .map(|t| self.ts_type(t, namespace))
+      .collect::<Vec<_>>()
+      .join(separator)
+  }
+
+  fn params(&self, params: &[ParamDef], namespace: &str) -> String {
+    params
+      .iter()
+      .map(|param| self.param(param, namespace))
+      .collect::<Vec<_>>()
+      .join(", ")
+  }
+
+  fn param(&self, param: &ParamDef, namespace: &str) -> String {
+    let ts_type = match param {
+      ParamDef::Array { ts_type, .. }
+      | ParamDef::Assign { ts_type, .. }
+      | ParamDef::Identifier { ts_type, .. }
+      | ParamDef::Object { ts_type, .. }
+      | ParamDef::Rest { ts_type, .. } => ts_type.as_ref(),
+    };
+    let mut out = param_name(param);
+    out.push_str(&self.type_annotation(ts_type, namespace));
+    if let ParamDef::Assign { right, .. } = param {
+      write!(out, " = {}", escape(right)).unwrap();
+    }
+    out
+  }
+
+  fn ts_type(&self, ts_type: &TsTypeDef, namespace: &str) -> String {
+    if let Some(keyword) = &ts_type.keyword {
+      escape(keyword)
+    } else if let Some(type_ref) = &ts_type.type_ref {
+      let mut out = self.link(&type_ref.type_name, namespace);
+      if let Some(params) = &type_ref.type_params {
+        out.push_str(&self.type_args(params, namespace));
+      }
+      out
+    } else if let Some(types) = &ts_type.union {
+      self.join_types(types, " \\| ", namespace)
+    } else if let Some(types) = &ts_type.intersection {
+      self.join_types(types, " & ", namespace)
+    } else if let Some(types) = &ts_type.tuple {
+      format!("\\[{}\\]", self.type_list(types, namespace))
+    } else if let Some(element) = &ts_type.array {
+      let rendered = self.ts_type(element, namespace);
+      if element.union.is_some()
+        || element.intersection.is_some()
+        || element.fn_or_constructor.is_some()
+      {
+        format!("({})\\[\\]", rendered)
+      } else {
+        format!("{}\\[\\]", rendered)
+      }
+    } else if let Some(inner) = &ts_type.parenthesized {
+      format!("({})", self.ts_type(inner, namespace))
+    } else if let Some(inner) = &ts_type.rest {
+      format!("...{}", self.ts_type(inner, namespace))
+    } else if let Some(inner) = &ts_type.optional {
+      format!("{}?", self.ts_type(inner, namespace))
+    } else if let Some(operator) = &ts_type.type_operator {
+      format!(
+        "{} {}",
+        escape(&operator.operator),
+        self.ts_type(&operator.ts_type, namespace)
+      )
+    } else if let Some(query) = &ts_type.type_query {
+      format!("**typeof** {}", self.link(query, namespace))
+    } else if let Some(access) = &ts_type.indexed_access {
+      format!(
+        "{}\\[{}\\]",
+        self.ts_type(&access.obj_type, namespace),
+        self.ts_type(&access.index_type, namespace)
+      )
+    } else if let Some(conditional) = &ts_type.conditional_type {
+      format!(
+        "{} **extends** {} ? {} : {}",
+        self.ts_type(&conditional.check_type, namespace),
+        self.ts_type(&conditional.extends_type, namespace),
+        self.ts_type(&conditional.true_type, namespace),
+        self.ts_type(&conditional.false_type, namespace)
+      )
+    } else if let Some(def) = &ts_type.fn_or_constructor {
+      format!(
+        "{}{}({}) => {}",
+        if def.constructor { "**new** " } else { "" },
+        self.type_params(&def.type_params, namespace),
+        self.params(&def.params, namespace),
+        self.ts_type(&def.ts_type, namespace)
+      )
+    } else if let Some(literal) = &ts_type.type_literal {
+      let mut entries = Vec::new();
+      for prop in &literal.properties {
+        entries.push(format!(
+          "{}{}",
+          member_name(&prop.name, prop.computed, prop.optional),
+          self.type_annotation(prop.ts_type.as_ref(), namespace)
+        ));
+      }
+      for method in &literal.methods {
+        entries.push(format!(
+          "{}{}",
+          escape(&method.name),
+          self.fn_signature(
+            &method.type_params,
+            &method.params,
+            method.return_type.as_ref(),
+            namespace
+          )
+        ));
+      }
+      for signature in &literal.call_signatures {
+        entries.push(self.fn_signature(
+          &signature.type_params,
+          &signature.params,
+          signature.ts_type.as_ref(),
+          namespace,
+        ));
+      }
+      for signature in &literal.index_signatures {
+        entries.push(self.index_signature(
+          signature.readonly,
+          &signature.params,
+          signature.ts_type.as_ref(),
+          namespace,
+        ));
+      }
+      if entries.is_empty() {
+        "{}".to_string()
+      } else {
+        format!("{{ {} }}", entries.join("; "))
+      }
+    } else if let Some(string) =
+      ts_type.literal.as_ref().and_then(|l| l.string.as_ref())
+    {
+      escape(&format!("{:?}", string))
+    } else {
+      escape(&ts_type.repr)
+    }
+  }
+}
+
+fn render_enum(out: &mut String, node: &DocNode) {
+  let members = match &node.enum_def {
+    Some(def) if !def.members.is_empty() => &def.members,
+    _ => return,
+  };
+  out.push_str("\n**Members**\n\n");
+  for member in members {
+    push_item(out, &escape(&member.name), None);
+  }
+}
+
+/// Appends a list item, with its documentation indented below it.
+fn push_item(out: &mut String, item: &str, doc: Option<&str>) {
+  writeln!(out, "- {}", item).unwrap();
+  if let Some(doc) = doc {
+    out.push('\n');
+    for line in doc.trim().lines() {
+      if line.is_empty() {
+        out.push('\n');
+      } else {
+        writeln!(out, "  {}", line).unwrap();
+      }
+    }
+    out.push('\n');
+  }
+}
+
+fn param_name(param: &ParamDef) -> String {
+  let (mut out, optional) = match param {
+    ParamDef::Array { optional, .. } => ("\\[...\\]".to_string(), *optional),
+    ParamDef::Assign { left, .. } => (param_name(left), false),
+    ParamDef::Identifier { name, optional, .. } => (escape(name), *optional),
+    ParamDef::Object { optional, .. } => ("{...}".to_string(), *optional),
+    ParamDef::Rest { arg, .. } => (format!("...{}", param_name(arg)), false),
+  };
+  if optional {
+    out.push('?');
+  }
+  out
+}
+
+fn member_name(name: &str, computed: bool, optional: bool) -> String {
+  let mut out = if computed {
+    format!("\\[{}\\]", escape(name))
+  } else {
+    escape(name)
+  };
+  if optional {
+    out.push('?');
+  }
+  out
+}
+
+fn modifiers(is_static: bool, is_abstract: bool) -> String {
+  let mut out = String::new();
+  if is_static {
+    out.push_str("**static** ");
+  }
+  if is_abstract {
+    out.push_str("**abstract** ");
+  }
+  out
+}
+
+fn accessibility(accessibility: &Option<Accessibility>) -> &'static str {
+  match accessibility {
+    Some(Accessibility::Private) => "**private** ",
+    Some(Accessibility::Protected) => "**protected** ",
+    _ => "",
+  }
+}
+
+fn js_doc(js_doc: &Option<String>) -> Option<&str> {
+  js_doc.as_deref().filter(|doc| !doc.trim().is_empty())
+}
+
+/// Returns the first paragraph of a JSDoc comment, joined into one line.
+fn first_paragraph(doc: &str) -> Option<String> {
+  let paragraph = doc
+    .trim()
+    .split("\n\n")
+    .next()?
+    .lines()
+    .map(str::trim)
+    .collect::<Vec<_>>()
+    .join(" ");
+  if paragraph.is_empty() {
+    None
+  } else {
+    Some(paragraph)
+  }
+}
+
+fn qualify(namespace: &str, name: &str) -> String {
+  if namespace.is_empty() {
+    name.to_string()
+  } else {
+    format!("{}.{}", namespace, name)
+  }
+}
+
+/// Escapes characters that Markdown would otherwise interpret in signatures.
+fn escape(text: &str) -> String {
+  let mut out = String::with_capacity(text.len());
+  for c in text.chars() {
+    if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|') {
+      out.push('\\');
+    }
+    out.push(c);
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::ast;
+  use crate::media_type::MediaType;
+  use crate::tools::doc::StubDocLoader;
+  use deno_doc::DocParser;
+
+  fn parse(source: &str) -> Vec<DocNode> {
+    DocParser::new(Box::new(StubDocLoader), false)
+      .parse_source("mod.ts", ast::get_syntax(&MediaType::TypeScript), source)
+      .unwrap()
+  }
+
+  const SOURCE: &str = r#"
+/**
+ * Greets a person.
+ *
+ * Returns the greeting.
+ */
+export function greet(person: Person): string {
+  return person.name ?? "";
+}
+
+export interface Person {
+  /** The full name. */
+  name?: string | undefined;
+}
+"#;
+
+  #[test]
+  fn render_single_document() {
+    let out = render_document("mod.ts", &parse(SOURCE));
+    assert!(out.starts_with("# mod.ts\n"));
+    assert!(out.contains("\n## Functions\n"));
+    assert!(out.contains("<a name=\"greet\"></a>\n\n### greet\n"));
+    assert!(
+      out.contains("**function** greet(person: [Person](#person)): string\n")
+    );
+    assert!(out.contains("\nGreets a person.\n\nReturns the greeting.\n"));
+    assert!(out.contains("- name?: string \\| undefined\n\n  The full name.\n"));
+    // Interfaces are listed after functions.
+    assert!(out.find("## Functions") < out.find("## Interfaces"));
+  }
+
+  #[test]
+  fn render_multiple_files() {
+    let files = render_files("mod.ts", &parse(SOURCE));
+    let names: Vec<&str> =
+      files.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["index.md", "greet.md", "Person.md"]);
+    assert!(files[0]
+      .1
+      .contains("- [greet](greet.md): Greets a person.\n"));
+    assert!(files[0].1.contains("- [Person](Person.md)\n"));
+    assert!(files[1].1.starts_with("[mod.ts](index.md)\n"));
+    assert!(files[1]
+      .1
+      .contains("**function** greet(person: [Person](Person.md)): string\n"));
+  }
+
+  #[test]
+  fn render_namespace_members() {
+    let nodes = parse(
+      r#"
+export namespace NS {
+  export type Id = number;
+  export let current: Id;
+}
+"#,
+    );
+
+    let out = render_document("mod.ts", &nodes);
+    assert!(out.contains("<a name=\"ns.current\"></a>\n\n#### NS.current\n"));
+    assert!(out.contains("**let** current: [Id](#ns.id)\n"));
+
+    let files = render_files("mod.ts", &nodes);
+    assert_eq!(files.len(), 2);
+    assert!(files[1].1.contains("**let** current: [Id](NS.md#ns.id)\n"));
+  }
+
+  #[test]
+  fn render_colliding_names() {
+    let nodes = parse(
+      r#"
+export class Foo {}
+export function foo(): Foo {
+  return new Foo();
+}
+export const index = 1;
+"#,
+    );
+
+    let out = render_document("mod.ts", &nodes);
+    assert!(out.contains("<a name=\"foo\"></a>\n\n### Foo\n"));
+    assert!(out.contains("<a name=\"foo-2\"></a>\n\n### foo\n"));
+    assert!(out.contains("**function** foo(): [Foo](#foo)\n"));
+
+    let files = render_files("mod.ts", &nodes);
+    let names: Vec<&str> =
+      files.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["index.md", "Foo.md", "index-2.md", "foo-2.md"]);
+  }
+
+  #[test]
+  fn escape_markdown() {
+    assert_eq!(escape("Array<T | U>"), "Array\\<T \\| U\\>");
+    assert_eq!(escape("__proto__"), "\\_\\_proto\\_\\_");
+  }
+}
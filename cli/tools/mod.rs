@@ -2,9 +2,11 @@
 
 pub mod coverage;
 pub mod doc;
+pub mod doc_markdown;
 pub mod fmt;
 pub mod installer;
 pub mod lint;
+pub mod pe;
 pub mod repl;
 pub mod standalone;
 pub mod test_runner;
@@ -0,0 +1,980 @@
+// Copyright 2018-2021 the Deno authors. All rights reserved. MIT license.
+
+//! Minimal editing of Windows PE executables. This is used by `deno compile`
+//! to set the icon, version information and subsystem of a Windows binary
+//! without relying on Windows-only tooling, so it also works when
+//! cross-compiling from other platforms.
+
+use deno_core::error::bail;
+use deno_core::error::generic_error;
+use deno_core::error::AnyError;
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+
+const IMAGE_SUBSYSTEM_WINDOWS_GUI: u16 = 2;
+const IMAGE_SUBSYSTEM_WINDOWS_CUI: u16 = 3;
+
+const IMAGE_DIRECTORY_ENTRY_RESOURCE: usize = 2;
+const IMAGE_DIRECTORY_ENTRY_SECURITY: usize = 4;
+
+const IMAGE_SCN_CNT_INITIALIZED_DATA: u32 = 0x0000_0040;
+const IMAGE_SCN_MEM_READ: u32 = 0x4000_0000;
+
+const RT_ICON: u16 = 3;
+const RT_GROUP_ICON: u16 = 14;
+const RT_VERSION: u16 = 16;
+
+/// en-US
+const DEFAULT_LANGUAGE: u16 = 0x0409;
+/// UTF-16
+const DEFAULT_CODEPAGE: u16 = 0x04b0;
+
+/// Name of the section that holds the rewritten resources. The original
+/// resource section is left in place, but is no longer referenced.
+const RESOURCE_SECTION_NAME: &[u8; 8] = b".rsrc2\0\0";
+
+#[derive(Debug, Default)]
+pub struct WindowsResources {
+  /// Contents of an `.ico` file to use as the application icon.
+  pub icon: Option<Vec<u8>>,
+  /// One of the `IMAGE_SUBSYSTEM_*` values, see `parse_subsystem()`.
+  pub subsystem: Option<u16>,
+  /// File and product version, see `parse_version()`.
+  pub version: Option<[u16; 4]>,
+  /// Entries of the version info string table, e.g. `ProductName`.
+  pub strings: Vec<(String, String)>,
+}
+
+pub fn parse_subsystem(subsystem: &str) -> Result<u16, AnyError> {
+  match subsystem {
+    "console" => Ok(IMAGE_SUBSYSTEM_WINDOWS_CUI),
+    "windows" => Ok(IMAGE_SUBSYSTEM_WINDOWS_GUI),
+    _ => bail!("Invalid subsystem \"{}\"", subsystem),
+  }
+}
+
+/// Parses a version of up to four dot separated numbers, e.g. `1.2.3`.
+pub fn parse_version(version: &str) -> Result<[u16; 4], AnyError> {
+  let mut parts = [0; 4];
+  for (i, part) in version.split('.').enumerate() {
+    if i >= parts.len() {
+      bail!("Invalid version \"{}\"", version);
+    }
+    parts[i] = part
+      .parse()
+      .map_err(|_| generic_error(format!("Invalid version \"{}\"", version)))?;
+  }
+  Ok(parts)
+}
+
+/// Applies the given resources to a Windows executable, returning the
+/// modified executable. Any Authenticode signature is removed, as it would no
+/// longer be valid.
+pub fn apply_windows_resources(
+  mut bin: Vec<u8>,
+  resources: &WindowsResources,
+) -> Result<Vec<u8>, AnyError> {
+  let layout = PeLayout::parse(&bin)?;
+
+  if let Some(subsystem) = resources.subsystem {
+    write_u16(&mut bin, layout.optional_header + 68, subsystem);
+  }
+
+  if resources.icon.is_none()
+    && resources.version.is_none()
+    && resources.strings.is_empty()
+  {
+    return Ok(bin);
+  }
+
+  let sections = layout.sections(&bin)?;
+  let (resource_rva, _) =
+    layout.data_directory(&bin, IMAGE_DIRECTORY_ENTRY_RESOURCE)?;
+  let mut tree = if resource_rva == 0 {
+    ResourceTree::new()
+  } else {
+    read_resources(&bin, &sections, resource_rva)?
+  };
+  if let Some(icon) = &resources.icon {
+    set_icon(&mut tree, icon)?;
+  }
+  if resources.version.is_some() || !resources.strings.is_empty() {
+    set_version_info(&mut tree, resources);
+  }
+
+  let (cert_offset, cert_size) =
+    layout.data_directory(&bin, IMAGE_DIRECTORY_ENTRY_SECURITY)?;
+  if cert_offset != 0 {
+    if cert_offset as usize + cert_size as usize == bin.len() {
+      bin.truncate(cert_offset as usize);
+    }
+    layout.set_data_directory(&mut bin, IMAGE_DIRECTORY_ENTRY_SECURITY, 0, 0);
+  }
+
+  let rva = layout.next_section_rva(&bin, &sections)?;
+  let data = write_resources(&tree, rva);
+  layout.append_section(&mut bin, &sections, rva, &data)?;
+  layout.set_data_directory(
+    &mut bin,
+    IMAGE_DIRECTORY_ENTRY_RESOURCE,
+    rva,
+    data.len() as u32,
+  );
+  // The checksum is only verified for drivers, and would be invalidated by
+  // the bundle appended to the binary anyway.
+  write_u32(&mut bin, layout.optional_header + 64, 0);
+
+  Ok(bin)
+}
+
+fn invalid_pe() -> AnyError {
+  generic_error("Invalid PE executable")
+}
+
+fn read_u16(bin: &[u8], offset: usize) -> Result<u16, AnyError> {
+  let bytes = bin.get(offset..offset + 2).ok_or_else(invalid_pe)?;
+  Ok(u16::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_u32(bin: &[u8], offset: usize) -> Result<u32, AnyError> {
+  let bytes = bin.get(offset..offset + 4).ok_or_else(invalid_pe)?;
+  Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn write_u16(bin: &mut [u8], offset: usize, value: u16) {
+  bin[offset..offset + 2].copy_from_slice(&value.to_le_bytes());
+}
+
+fn write_u32(bin: &mut [u8], offset: usize, value: u32) {
+  bin[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+fn align(value: usize, alignment: usize) -> usize {
+  (value + alignment - 1) / alignment * alignment
+}
+
+struct Section {
+  virtual_size: u32,
+  virtual_address: u32,
+  size_of_raw_data: u32,
+  pointer_to_raw_data: u32,
+}
+
+/// Offsets of the headers of a PE executable that are needed to edit it.
+struct PeLayout {
+  coff_header: usize,
+  optional_header: usize,
+  data_directories: usize,
+  number_of_data_directories: usize,
+  section_table: usize,
+  number_of_sections: usize,
+}
+
+impl PeLayout {
+  fn parse(bin: &[u8]) -> Result<Self, AnyError> {
+    if bin.get(0..2) != Some(b"MZ") {
+      return Err(invalid_pe());
+    }
+    let signature = read_u32(bin, 0x3c)? as usize;
+    if bin.get(signature..signature + 4) != Some(b"PE\0\0") {
+      return Err(invalid_pe());
+    }
+    let coff_header = signature + 4;
+    let number_of_sections = read_u16(bin, coff_header + 2)? as usize;
+    let size_of_optional_header = read_u16(bin, coff_header + 16)? as usize;
+    let optional_header = coff_header + 20;
+    let data_directories = match read_u16(bin, optional_header)? {
+      // PE32
+      0x10b => optional_header + 96,
+      // PE32+
+      0x20b => optional_header + 112,
+      _ => return Err(invalid_pe()),
+    };
+    let number_of_data_directories =
+      read_u32(bin, data_directories - 4)? as usize;
+    Ok(Self {
+      coff_header,
+      optional_header,
+      data_directories,
+      number_of_data_directories,
+      section_table: optional_header + size_of_optional_header,
+      number_of_sections,
+    })
+  }
+
+  fn section_alignment(&self, bin: &[u8]) -> Result<usize, AnyError> {
+    Ok(read_u32(bin, self.optional_header + 32)? as usize)
+  }
+
+  fn file_alignment(&self, bin: &[u8]) -> Result<usize, AnyError> {
+    Ok(read_u32(bin, self.optional_header + 36)? as usize)
+  }
+
+  fn sections(&self, bin: &[u8]) -> Result<Vec<Section>, AnyError> {
+    (0..self.number_of_sections)
+      .map(|i| {
+        let header = self.section_table + i * 40;
+        Ok(Section {
+          virtual_size: read_u32(bin, header + 8)?,
+          virtual_address: read_u32(bin, header + 12)?,
+          size_of_raw_data: read_u32(bin, header + 16)?,
+          pointer_to_raw_data: read_u32(bin, header + 20)?,
+        })
+      })
+      .collect()
+  }
+
+  fn data_directory(
+    &self,
+    bin: &[u8],
+    index: usize,
+  ) -> Result<(u32, u32), AnyError> {
+    if index >= self.number_of_data_directories {
+      return Ok((0, 0));
+    }
+    let entry = self.data_directories + index * 8;
+    Ok((read_u32(bin, entry)?, read_u32(bin, entry + 4)?))
+  }
+
+  fn set_data_directory(
+    &self,
+    bin: &mut [u8],
+    index: usize,
+    address: u32,
+    size: u32,
+  ) {
+    let entry = self.data_directories + index * 8;
+    write_u32(bin, entry, address);
+    write_u32(bin, entry + 4, size);
+  }
+
+  /// Returns the address at which a new section can be mapped, after all
+  /// existing sections.
+  fn next_section_rva(
+    &self,
+    bin: &[u8],
+    sections: &[Section],
+  ) -> Result<u32, AnyError> {
+    let end = sections
+      .iter()
+      .map(|s| s.virtual_address + s.virtual_size.max(s.size_of_raw_data))
+      .max()
+      .unwrap_or(0);
+    Ok(align(end as usize, self.section_alignment(bin)?) as u32)
+  }
+
+  /// Appends a new read-only data section, mapped at `rva`, to the end of the
+  /// executable.
+  fn append_section(
+    &self,
+    bin: &mut Vec<u8>,
+    sections: &[Section],
+    rva: u32,
+    data: &[u8],
+  ) -> Result<(), AnyError> {
+    let section_alignment = self.section_alignment(bin)?;
+    let file_alignment = self.file_alignment(bin)?;
+    let size_of_headers = read_u32(bin, self.optional_header + 60)? as usize;
+    let header = self.section_table + self.number_of_sections * 40;
+    let first_section = sections
+      .iter()
+      .filter(|s| s.pointer_to_raw_data != 0)
+      .map(|s| s.pointer_to_raw_data as usize)
+      .min()
+      .unwrap_or(size_of_headers);
+    if header + 40 > size_of_headers.min(first_section) {
+      bail!("No room for an additional section header in the executable");
+    }
+
+    let virtual_address = rva as usize;
+    let pointer_to_raw_data = align(bin.len(), file_alignment);
+    let size_of_raw_data = align(data.len(), file_alignment);
+
+    bin[header..header + 8].copy_from_slice(RESOURCE_SECTION_NAME);
+    write_u32(bin, header + 8, data.len() as u32);
+    write_u32(bin, header + 12, virtual_address as u32);
+    write_u32(bin, header + 16, size_of_raw_data as u32);
+    write_u32(bin, header + 20, pointer_to_raw_data as u32);
+    for offset in (24..36).step_by(4) {
+      write_u32(bin, header + offset, 0);
+    }
+    write_u32(
+      bin,
+      header + 36,
+      IMAGE_SCN_CNT_INITIALIZED_DATA | IMAGE_SCN_MEM_READ,
+    );
+    write_u16(
+      bin,
+      self.coff_header + 2,
+      (self.number_of_sections + 1) as u16,
+    );
+    write_u32(
+      bin,
+      self.optional_header + 56,
+      align(virtual_address + data.len(), section_alignment) as u32,
+    );
+
+    bin.resize(pointer_to_raw_data, 0);
+    bin.extend_from_slice(data);
+    bin.resize(pointer_to_raw_data + size_of_raw_data, 0);
+    Ok(())
+  }
+}
+
+/// Resource directory entries are identified either by name or by numeric id.
+/// Named entries sort before numeric ones, as required by the format.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum ResourceName {
+  Name(Vec<u16>),
+  Id(u16),
+}
+
+#[derive(Debug)]
+struct ResourceData {
+  data: Vec<u8>,
+  codepage: u32,
+}
+
+/// Type -> name -> language -> data.
+type ResourceTree =
+  BTreeMap<ResourceName, BTreeMap<ResourceName, BTreeMap<u16, ResourceData>>>;
+
+fn rva_to_offset(sections: &[Section], rva: u32) -> Result<usize, AnyError> {
+  sections
+    .iter()
+    .find(|s| {
+      rva >= s.virtual_address
+        && rva - s.virtual_address < s.virtual_size.max(s.size_of_raw_data)
+    })
+    .map(|s| (s.pointer_to_raw_data + (rva - s.virtual_address)) as usize)
+    .ok_or_else(invalid_pe)
+}
+
+/// Reads the entries of the resource directory at `offset`, returning their
+/// names and raw `OffsetToData` fields.
+fn read_resource_directory(
+  bin: &[u8],
+  base: usize,
+  offset: usize,
+) -> Result<Vec<(ResourceName, u32)>, AnyError> {
+  let directory = base + offset;
+  let count = read_u16(bin, directory + 12)? as usize
+    + read_u16(bin, directory + 14)? as usize;
+  let mut entries = Vec::with_capacity(count);
+  for i in 0..count {
+    let entry = directory + 16 + i * 8;
+    let name = read_u32(bin, entry)?;
+    let name = if name & 0x8000_0000 != 0 {
+      let string = base + (name & 0x7fff_ffff) as usize;
+      let len = read_u16(bin, string)? as usize;
+      let chars = (0..len)
+        .map(|j| read_u16(bin, string + 2 + j * 2))
+        .collect::<Result<_, _>>()?;
+      ResourceName::Name(chars)
+    } else {
+      ResourceName::Id(name as u16)
+    };
+    entries.push((name, read_u32(bin, entry + 4)?));
+  }
+  Ok(entries)
+}
+
+fn subdirectory(offset_to_data: u32) -> Result<usize, AnyError> {
+  if offset_to_data & 0x8000_0000 == 0 {
+    return Err(invalid_pe());
+  }
+  Ok((offset_to_data & 0x7fff_ffff) as usize)
+}
+
+fn read_resources(
+  bin: &[u8],
+  sections: &[Section],
+  rva: u32,
+) -> Result<ResourceTree, AnyError> {
+  let base = rva_to_offset(sections, rva)?;
+  let mut tree = ResourceTree::new();
+  for (type_, types) in read_resource_directory(bin, base, 0)? {
+    let names = tree.entry(type_).or_default();
+    for (name, langs) in
+      read_resource_directory(bin, base, subdirectory(types)?)?
+    {
+      let entries = names.entry(name).or_default();
+      for (lang, entry) in
+        read_resource_directory(bin, base, subdirectory(langs)?)?
+      {
+        let lang = match lang {
+          ResourceName::Id(lang) => lang,
+          ResourceName::Name(_) => return Err(invalid_pe()),
+        };
+        if entry & 0x8000_0000 != 0 {
+          return Err(invalid_pe());
+        }
+        let entry = base + entry as usize;
+        let offset = rva_to_offset(sections, read_u32(bin, entry)?)?;
+        let size = read_u32(bin, entry + 4)? as usize;
+        let data = bin.get(offset..offset + size).ok_or_else(invalid_pe)?;
+        entries.insert(
+          lang,
+          ResourceData {
+            data: data.to_vec(),
+            codepage: read_u32(bin, entry + 8)?,
+          },
+        );
+      }
+    }
+  }
+  Ok(tree)
+}
+
+/// Serializes the resource tree into the contents of a resource section that
+/// will be loaded at `rva`.
+fn write_resources(tree: &ResourceTree, rva: u32) -> Vec<u8> {
+  // Lay out all directory tables first, then the data entries, the name
+  // strings and finally the resource data itself.
+  let mut offset = 16 + 8 * tree.len();
+  let mut type_directories = vec![];
+  for names in tree.values() {
+    type_directories.push(offset);
+    offset += 16 + 8 * names.len();
+  }
+  let mut name_directories = vec![];
+  for langs in tree.values().flat_map(|names| names.values()) {
+    name_directories.push(offset);
+    offset += 16 + 8 * langs.len();
+  }
+  let leaves: Vec<&ResourceData> = tree
+    .values()
+    .flat_map(|names| names.values())
+    .flat_map(|langs| langs.values())
+    .collect();
+  let data_entries = offset;
+  offset += 16 * leaves.len();
+  let mut strings = BTreeMap::new();
+  for name in tree
+    .iter()
+    .flat_map(|(type_, names)| std::iter::once(type_).chain(names.keys()))
+  {
+    if let ResourceName::Name(chars) = name {
+      if !strings.contains_key(chars) {
+        strings.insert(chars.clone(), offset);
+        offset += 2 + 2 * chars.len();
+      }
+    }
+  }
+  let mut data_offsets = vec![];
+  for leaf in &leaves {
+    offset = align(offset, 8);
+    data_offsets.push(offset);
+    offset += leaf.data.len();
+  }
+
+  let mut out = vec![0; offset];
+  for (chars, offset) in &strings {
+    write_u16(&mut out, *offset, chars.len() as u16);
+    for (i, c) in chars.iter().enumerate() {
+      write_u16(&mut out, offset + 2 + i * 2, *c);
+    }
+  }
+  let name_field = |name: &ResourceName| match name {
+    ResourceName::Name(chars) => 0x8000_0000 | strings[chars] as u32,
+    ResourceName::Id(id) => *id as u32,
+  };
+
+  let mut directories = vec![(0, tree.keys().collect::<Vec<_>>())];
+  let mut children = vec![];
+  for (i, names) in tree.values().enumerate() {
+    children.push(0x8000_0000 | type_directories[i] as u32);
+    directories.push((type_directories[i], names.keys().collect()));
+  }
+  for offset in &name_directories {
+    children.push(0x8000_0000 | *offset as u32);
+  }
+  for i in 0..leaves.len() {
+    children.push((data_entries + i * 16) as u32);
+  }
+  // Language directories only contain numeric ids.
+  let lang_names: Vec<Vec<ResourceName>> = tree
+    .values()
+    .flat_map(|names| names.values())
+    .map(|langs| langs.keys().map(|l| ResourceName::Id(*l)).collect())
+    .collect();
+  for (i, names) in lang_names.iter().enumerate() {
+    directories.push((name_directories[i], names.iter().collect()));
+  }
+
+  let mut children = children.into_iter();
+  for (offset, names) in directories {
+    let named = names
+      .iter()
+      .filter(|n| matches!(n, ResourceName::Name(_)))
+      .count();
+    write_u16(&mut out, offset + 12, named as u16);
+    write_u16(&mut out, offset + 14, (names.len() - named) as u16);
+    for (i, name) in names.into_iter().enumerate() {
+      let entry = offset + 16 + i * 8;
+      write_u32(&mut out, entry, name_field(name));
+      write_u32(&mut out, entry + 4, children.next().unwrap());
+    }
+  }
+
+  for (i, leaf) in leaves.iter().enumerate() {
+    let entry = data_entries + i * 16;
+    write_u32(&mut out, entry, rva + data_offsets[i] as u32);
+    write_u32(&mut out, entry + 4, leaf.data.len() as u32);
+    write_u32(&mut out, entry + 8, leaf.codepage);
+    out[data_offsets[i]..data_offsets[i] + leaf.data.len()]
+      .copy_from_slice(&leaf.data);
+  }
+
+  out
+}
+
+/// Replaces all icons with the images of the given `.ico` file.
+fn set_icon(tree: &mut ResourceTree, ico: &[u8]) -> Result<(), AnyError> {
+  let invalid_icon = || generic_error("Invalid icon file");
+  let header = ico.get(0..6).ok_or_else(invalid_icon)?;
+  let count = read_u16(header, 4)? as usize;
+  if read_u16(header, 0)? != 0 || read_u16(header, 2)? != 1 || count == 0 {
+    return Err(invalid_icon());
+  }
+
+  // The group icon has the same layout as the icon file header, except that
+  // each entry references an RT_ICON resource instead of a file offset.
+  let mut group = header.to_vec();
+  let mut icons = BTreeMap::new();
+  for i in 0..count {
+    let entry = ico
+      .get(6 + i * 16..6 + (i + 1) * 16)
+      .ok_or_else(invalid_icon)?;
+    let size = read_u32(entry, 8)? as usize;
+    let offset = read_u32(entry, 12)? as usize;
+    let image = ico.get(offset..offset + size).ok_or_else(invalid_icon)?;
+    let id = (i + 1) as u16;
+    group.extend_from_slice(&entry[0..12]);
+    group.extend_from_slice(&id.to_le_bytes());
+    icons.insert(ResourceName::Id(id), language_map(image.to_vec()));
+  }
+
+  tree.insert(ResourceName::Id(RT_ICON), icons);
+  let mut groups = BTreeMap::new();
+  groups.insert(ResourceName::Id(1), language_map(group));
+  tree.insert(ResourceName::Id(RT_GROUP_ICON), groups);
+  Ok(())
+}
+
+fn language_map(data: Vec<u8>) -> BTreeMap<u16, ResourceData> {
+  let mut langs = BTreeMap::new();
+  langs.insert(DEFAULT_LANGUAGE, ResourceData { data, codepage: 0 });
+  langs
+}
+
+/// Merges the given version and strings into the existing version
+/// information, if any, and replaces it with the resulting `VS_VERSIONINFO`.
+fn set_version_info(tree: &mut ResourceTree, resources: &WindowsResources) {
+  let existing = tree
+    .get(&ResourceName::Id(RT_VERSION))
+    .and_then(|names| names.values().next())
+    .and_then(|languages| languages.values().next())
+    .and_then(|resource| read_version_node(&resource.data).ok());
+
+  let mut fixed_file_info = match &existing {
+    Some(node)
+      if node.value.len() == 52
+        && read_u32(node.value, 0).unwrap() == 0xfeef_04bd =>
+    {
+      node.value.to_vec()
+    }
+    _ => {
+      let mut fixed_file_info = vec![];
+      for value in &[
+        0xfeef_04bd_u32, // dwSignature
+        0x0001_0000,     // dwStrucVersion
+        0,               // dwFileVersionMS
+        0,               // dwFileVersionLS
+        0,               // dwProductVersionMS
+        0,               // dwProductVersionLS
+        0x3f,            // dwFileFlagsMask
+        0,               // dwFileFlags
+        0x0004_0004,     // dwFileOS: VOS_NT_WINDOWS32
+        1,               // dwFileType: VFT_APP
+        0,               // dwFileSubtype
+        0,               // dwFileDateMS
+        0,               // dwFileDateLS
+      ] {
+        fixed_file_info.extend_from_slice(&value.to_le_bytes());
+      }
+      fixed_file_info
+    }
+  };
+
+  let mut strings = existing
+    .as_ref()
+    .and_then(|node| node.children.iter().find(|c| c.key == "StringFileInfo"))
+    .and_then(|node| node.children.first())
+    .map(|table| {
+      table
+        .children
+        .iter()
+        .map(|string| (string.key.clone(), utf16_value(string.value)))
+        .collect::<Vec<_>>()
+    })
+    .unwrap_or_default();
+  let mut new_strings = resources.strings.clone();
+  if let Some(version) = resources.version {
+    let version_string = format!(
+      "{}.{}.{}.{}",
+      version[0], version[1], version[2], version[3]
+    );
+    for key in &["FileVersion", "ProductVersion"] {
+      if !new_strings.iter().any(|(k, _)| k == key) {
+        new_strings.push((key.to_string(), version_string.clone()));
+      }
+    }
+
+    let ms = ((version[0] as u32) << 16) | version[1] as u32;
+    let ls = ((version[2] as u32) << 16) | version[3] as u32;
+    // dwFileVersionMS, dwFileVersionLS, dwProductVersionMS and
+    // dwProductVersionLS.
+    for &(offset, value) in &[(8, ms), (12, ls), (16, ms), (20, ls)] {
+      write_u32(&mut fixed_file_info, offset, value);
+    }
+  }
+  for (key, value) in new_strings {
+    match strings.iter_mut().find(|(k, _)| *k == key) {
+      Some(string) => string.1 = value,
+      None => strings.push((key, value)),
+    }
+  }
+
+  let string_table = strings
+    .iter()
+    .map(|(key, value)| {
+      let value = utf16z(value);
+      version_node(key, &value, (value.len() / 2) as u16, true, &[])
+    })
+    .collect::<Vec<_>>();
+  let translation = [
+    DEFAULT_LANGUAGE.to_le_bytes(),
+    DEFAULT_CODEPAGE.to_le_bytes(),
+  ]
+  .concat();
+  let version_info = version_node(
+    "VS_VERSION_INFO",
+    &fixed_file_info,
+    fixed_file_info.len() as u16,
+    false,
+    &[
+      version_node(
+        "StringFileInfo",
+        &[],
+        0,
+        true,
+        &[version_node(
+          &format!("{:04x}{:04x}", DEFAULT_LANGUAGE, DEFAULT_CODEPAGE),
+          &[],
+          0,
+          true,
+          &string_table,
+        )],
+      ),
+      version_node(
+        "VarFileInfo",
+        &[],
+        0,
+        true,
+        &[version_node("Translation", &translation, 4, false, &[])],
+      ),
+    ],
+  );
+
+  let mut versions = BTreeMap::new();
+  versions.insert(ResourceName::Id(1), language_map(version_info));
+  tree.insert(ResourceName::Id(RT_VERSION), versions);
+}
+
+/// A node of a `VS_VERSIONINFO` resource, see `version_node()`.
+struct VersionNode<'a> {
+  key: String,
+  value: &'a [u8],
+  children: Vec<VersionNode<'a>>,
+}
+
+fn read_version_node(data: &[u8]) -> Result<VersionNode<'_>, AnyError> {
+  let len = read_u16(data, 0)? as usize;
+  let value_length = read_u16(data, 2)? as usize;
+  let text = read_u16(data, 4)? == 1;
+  let data = data.get(..len).ok_or_else(invalid_pe)?;
+
+  let mut offset = 6;
+  let mut key = vec![];
+  loop {
+    let c = read_u16(data, offset)?;
+    offset += 2;
+    if c == 0 {
+      break;
+    }
+    key.push(c);
+  }
+  // The value length of text nodes is in characters, though some tools
+  // write it in bytes.
+  let value_start = align(offset, 4).min(len);
+  let value_end =
+    (value_start + if text { value_length * 2 } else { value_length }).min(len);
+  let value = &data[value_start..value_end];
+
+  let mut children = vec![];
+  let mut offset = align(value_end, 4);
+  while offset < len {
+    let child_len = read_u16(data, offset)? as usize;
+    if child_len == 0 {
+      return Err(invalid_pe());
+    }
+    children.push(read_version_node(&data[offset..])?);
+    offset = align(offset + child_len, 4);
+  }
+
+  Ok(VersionNode {
+    key: String::from_utf16_lossy(&key),
+    value,
+    children,
+  })
+}
+
+/// Decodes the value of a text node, up to its terminating null.
+fn utf16_value(value: &[u8]) -> String {
+  let chars = value
+    .chunks_exact(2)
+    .map(|c| u16::from_le_bytes([c[0], c[1]]))
+    .take_while(|c| *c != 0)
+    .collect::<Vec<_>>();
+  String::from_utf16_lossy(&chars)
+}
+
+fn utf16z(s: &str) -> Vec<u8> {
+  let mut out = vec![];
+  for c in s.encode_utf16().chain(std::iter::once(0)) {
+    out.extend_from_slice(&c.to_le_bytes());
+  }
+  out
+}
+
+fn pad4(buf: &mut Vec<u8>) {
+  buf.resize(align(buf.len(), 4), 0);
+}
+
+/// Builds one of the nested structures of a `VS_VERSIONINFO` resource, which
+/// all share the same header of length, value length, type and key.
+fn version_node(
+  key: &str,
+  value: &[u8],
+  value_length: u16,
+  text: bool,
+  children: &[Vec<u8>],
+) -> Vec<u8> {
+  let mut out = vec![0, 0];
+  out.extend_from_slice(&value_length.to_le_bytes());
+  out.extend_from_slice(&(text as u16).to_le_bytes());
+  out.extend_from_slice(&utf16z(key));
+  pad4(&mut out);
+  out.extend_from_slice(value);
+  for child in children {
+    pad4(&mut out);
+    out.extend_from_slice(child);
+  }
+  let len = out.len() as u16;
+  write_u16(&mut out, 0, len);
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Builds a minimal PE32+ image with a single section and room for more
+  /// section headers.
+  fn create_pe() -> Vec<u8> {
+    let mut bin = vec![0; 0x400];
+    bin[0..2].copy_from_slice(b"MZ");
+    write_u32(&mut bin, 0x3c, 0x40);
+    bin[0x40..0x44].copy_from_slice(b"PE\0\0");
+    let coff = 0x44;
+    write_u16(&mut bin, coff, 0x8664);
+    write_u16(&mut bin, coff + 2, 1);
+    write_u16(&mut bin, coff + 16, 240);
+    let opt = coff + 20;
+    write_u16(&mut bin, opt, 0x20b);
+    write_u32(&mut bin, opt + 32, 0x1000);
+    write_u32(&mut bin, opt + 36, 0x200);
+    write_u32(&mut bin, opt + 56, 0x2000);
+    write_u32(&mut bin, opt + 60, 0x400);
+    write_u16(&mut bin, opt + 68, IMAGE_SUBSYSTEM_WINDOWS_CUI);
+    write_u32(&mut bin, opt + 108, 16);
+    let section = opt + 240;
+    bin[section..section + 8].copy_from_slice(b".text\0\0\0");
+    write_u32(&mut bin, section + 8, 0x10);
+    write_u32(&mut bin, section + 12, 0x1000);
+    write_u32(&mut bin, section + 16, 0x200);
+    write_u32(&mut bin, section + 20, 0x400);
+    bin.resize(0x600, 0xcc);
+    bin
+  }
+
+  fn create_ico() -> Vec<u8> {
+    let mut ico = vec![0, 0, 1, 0, 1, 0];
+    ico.extend_from_slice(&[16, 16, 0, 0, 1, 0, 32, 0]);
+    ico.extend_from_slice(&4u32.to_le_bytes());
+    ico.extend_from_slice(&22u32.to_le_bytes());
+    ico.extend_from_slice(b"icon");
+    ico
+  }
+
+  #[test]
+  fn test_parse_version() {
+    assert_eq!(parse_version("1.2.3").unwrap(), [1, 2, 3, 0]);
+    assert_eq!(parse_version("1.2.3.4").unwrap(), [1, 2, 3, 4]);
+    assert!(parse_version("1.2.3.4.5").is_err());
+    assert!(parse_version("1.x").is_err());
+  }
+
+  #[test]
+  fn test_subsystem() {
+    let resources = WindowsResources {
+      subsystem: Some(parse_subsystem("windows").unwrap()),
+      ..Default::default()
+    };
+    let bin = apply_windows_resources(create_pe(), &resources).unwrap();
+    let layout = PeLayout::parse(&bin).unwrap();
+    assert_eq!(
+      read_u16(&bin, layout.optional_header + 68).unwrap(),
+      IMAGE_SUBSYSTEM_WINDOWS_GUI
+    );
+    assert_eq!(bin.len(), 0x600);
+  }
+
+  #[test]
+  fn test_resources() {
+    let resources = WindowsResources {
+      icon: Some(create_ico()),
+      version: Some([1, 2, 3, 0]),
+      strings: vec![("ProductName".to_string(), "Example".to_string())],
+      ..Default::default()
+    };
+    let bin = apply_windows_resources(create_pe(), &resources).unwrap();
+    let layout = PeLayout::parse(&bin).unwrap();
+    let sections = layout.sections(&bin).unwrap();
+    assert_eq!(sections.len(), 2);
+    assert_eq!(sections[1].virtual_address, 0x2000);
+    assert_eq!(read_u32(&bin, layout.optional_header + 56).unwrap(), 0x3000);
+
+    let (rva, _) = layout
+      .data_directory(&bin, IMAGE_DIRECTORY_ENTRY_RESOURCE)
+      .unwrap();
+    let tree = read_resources(&bin, &sections, rva).unwrap();
+    assert_eq!(
+      tree[&ResourceName::Id(RT_ICON)][&ResourceName::Id(1)][&DEFAULT_LANGUAGE]
+        .data,
+      b"icon"
+    );
+    let group = &tree[&ResourceName::Id(RT_GROUP_ICON)][&ResourceName::Id(1)]
+      [&DEFAULT_LANGUAGE]
+      .data;
+    assert_eq!(group.len(), 6 + 14);
+    assert_eq!(read_u16(group, 18).unwrap(), 1);
+    let version = &tree[&ResourceName::Id(RT_VERSION)][&ResourceName::Id(1)]
+      [&DEFAULT_LANGUAGE]
+      .data;
+    assert_eq!(read_u16(version, 0).unwrap() as usize, version.len());
+    assert_eq!(read_u32(version, 40).unwrap(), 0xfeef_04bd);
+    assert_eq!(read_u32(version, 48).unwrap(), 0x0001_0002);
+    assert_eq!(read_u32(version, 52).unwrap(), 0x0003_0000);
+
+    // Applying resources again replaces the previous ones.
+    let resources = WindowsResources {
+      version: Some([2, 0, 0, 0]),
+      ..Default::default()
+    };
+    let bin = apply_windows_resources(bin, &resources).unwrap();
+    let layout = PeLayout::parse(&bin).unwrap();
+    let sections = layout.sections(&bin).unwrap();
+    let (rva, _) = layout
+      .data_directory(&bin, IMAGE_DIRECTORY_ENTRY_RESOURCE)
+      .unwrap();
+    let tree = read_resources(&bin, &sections, rva).unwrap();
+    assert_eq!(tree.len(), 3);
+    let version = &tree[&ResourceName::Id(RT_VERSION)][&ResourceName::Id(1)]
+      [&DEFAULT_LANGUAGE]
+      .data;
+    assert_eq!(read_u32(version, 48).unwrap(), 0x0002_0000);
+  }
+
+  #[test]
+  fn test_version_strings_keep_version() {
+    let resources = WindowsResources {
+      version: Some([1, 2, 3, 4]),
+      strings: vec![("ProductName".to_string(), "Example".to_string())],
+      ..Default::default()
+    };
+    let bin = apply_windows_resources(create_pe(), &resources).unwrap();
+
+    let resources = WindowsResources {
+      strings: vec![
+        ("CompanyName".to_string(), "Deno".to_string()),
+        ("ProductName".to_string(), "Other".to_string()),
+      ],
+      ..Default::default()
+    };
+    let bin = apply_windows_resources(bin, &resources).unwrap();
+    let layout = PeLayout::parse(&bin).unwrap();
+    let sections = layout.sections(&bin).unwrap();
+    let (rva, _) = layout
+      .data_directory(&bin, IMAGE_DIRECTORY_ENTRY_RESOURCE)
+      .unwrap();
+    let tree = read_resources(&bin, &sections, rva).unwrap();
+    let version = &tree[&ResourceName::Id(RT_VERSION)][&ResourceName::Id(1)]
+      [&DEFAULT_LANGUAGE]
+      .data;
+    let node = read_version_node(version).unwrap();
+    assert_eq!(read_u32(node.value, 8).unwrap(), 0x0001_0002);
+    assert_eq!(read_u32(node.value, 12).unwrap(), 0x0003_0004);
+    assert_eq!(read_u32(node.value, 16).unwrap(), 0x0001_0002);
+    assert_eq!(read_u32(node.value, 20).unwrap(), 0x0003_0004);
+    let strings = node.children[0].children[0]
+      .children
+      .iter()
+      .map(|string| (string.key.as_str(), utf16_value(string.value)))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      strings,
+      vec![
+        ("ProductName", "Other".to_string()),
+        ("FileVersion", "1.2.3.4".to_string()),
+        ("ProductVersion", "1.2.3.4".to_string()),
+        ("CompanyName", "Deno".to_string()),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_named_resources_roundtrip() {
+    let mut tree = ResourceTree::new();
+    let name = ResourceName::Name("DENO".encode_utf16().collect());
+    let mut names = BTreeMap::new();
+    names.insert(name.clone(), language_map(b"named".to_vec()));
+    names.insert(ResourceName::Id(7), language_map(b"numbered".to_vec()));
+    tree.insert(ResourceName::Id(10), names);
+    let section = write_resources(&tree, 0x1000);
+    let sections = [Section {
+      virtual_size: section.len() as u32,
+      virtual_address: 0x1000,
+      size_of_raw_data: section.len() as u32,
+      pointer_to_raw_data: 0,
+    }];
+    let read = read_resources(&section, &sections, 0x1000).unwrap();
+    let names = &read[&ResourceName::Id(10)];
+    assert_eq!(names[&name][&DEFAULT_LANGUAGE].data, b"named");
+    assert_eq!(
+      names[&ResourceName::Id(7)][&DEFAULT_LANGUAGE].data,
+      b"numbered"
+    );
+  }
+}
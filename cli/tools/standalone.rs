@@ -19,6 +19,25 @@ use std::path::PathBuf;
 
 use crate::standalone::Metadata;
 use crate::standalone::MAGIC_TRAILER;
+use crate::tools::pe;
+
+/// Windows specific options of `deno compile`.
+#[derive(Debug, Default)]
+pub struct WindowsOptions {
+  pub icon: Option<PathBuf>,
+  pub subsystem: Option<String>,
+  pub version: Option<String>,
+  pub metadata: Vec<String>,
+}
+
+impl WindowsOptions {
+  fn is_empty(&self) -> bool {
+    self.icon.is_none()
+      && self.subsystem.is_none()
+      && self.version.is_none()
+      && self.metadata.is_empty()
+  }
+}
 
 pub async fn get_base_binary(
   deno_dir: &DenoDir,
@@ -80,6 +99,51 @@ async fn download_base_binary(
   Ok(())
 }
 
+/// Sets the icon, version information and subsystem of the base binary if
+/// the target is Windows.
+pub fn apply_windows_options(
+  original_bin: Vec<u8>,
+  target: Option<&str>,
+  options: WindowsOptions,
+) -> Result<Vec<u8>, AnyError> {
+  if options.is_empty() {
+    return Ok(original_bin);
+  }
+  let is_windows = match target {
+    Some(target) => target.contains("windows"),
+    None => cfg!(windows),
+  };
+  if !is_windows {
+    bail!(
+      "The --windows-* options can only be used when compiling for Windows."
+    );
+  }
+
+  let resources = pe::WindowsResources {
+    icon: match options.icon {
+      Some(icon) => Some(read(icon)?),
+      None => None,
+    },
+    subsystem: match options.subsystem {
+      Some(subsystem) => Some(pe::parse_subsystem(&subsystem)?),
+      None => None,
+    },
+    version: match options.version {
+      Some(version) => Some(pe::parse_version(&version)?),
+      None => None,
+    },
+    strings: options
+      .metadata
+      .iter()
+      .filter_map(|entry| {
+        let (key, value) = entry.split_at(entry.find('=')?);
+        Some((key.to_string(), value[1..].to_string()))
+      })
+      .collect(),
+  };
+  pe::apply_windows_resources(original_bin, &resources)
+}
+
 /// This functions creates a standalone deno binary by appending a bundle
 /// and magic trailer to the currently executing binary.
 pub fn create_standalone_binary(
@@ -8,6 +8,7 @@ use crate::fs_util::collect_files;
 use crate::fs_util::normalize_path;
 use crate::media_type::MediaType;
 use crate::module_graph;
+use crate::ops::testing::TestTimedOut;
 use crate::program_state::ProgramState;
 use crate::tokio_util;
 use crate::tools::coverage::CoverageCollector;
@@ -301,9 +302,12 @@ pub async fn run_test_file(
 
   worker.execute_module(&test_module).await?;
 
-  worker
-    .run_event_loop(maybe_coverage_collector.is_none())
-    .await?;
+  let timed_out = worker.js_runtime.op_state().borrow().has::<TestTimedOut>();
+  if !timed_out {
+    worker
+      .run_event_loop(maybe_coverage_collector.is_none())
+      .await?;
+  }
   worker.execute_script(
     &located_script_name!(),
     "window.dispatchEvent(new Event('unload'))",
@@ -334,6 +338,8 @@ pub async fn run_tests(
   allow_none: bool,
   filter: Option<String>,
   shuffle: Option<u64>,
+  shard: Option<(usize, usize)>,
+  timeout: Option<u64>,
   concurrent_jobs: usize,
 ) -> Result<bool, AnyError> {
   let test_modules = if let Some(seed) = shuffle {
@@ -452,6 +458,11 @@ pub async fn run_tests(
       "disableLog": quiet,
       "filter": filter,
       "shuffle": shuffle,
+      "shard": shard.map(|(index, count)| json!({
+        "index": index,
+        "count": count,
+      })),
+      "timeout": timeout,
   });
 
   let test_module = deno_core::resolve_path("$deno$test.js")?;
@@ -59,6 +59,7 @@
    * @property {number} redirectCount
    * @property {string[]} urlList
    * @property {number | null} clientRid NOTE: non standard extension for `Deno.HttpClient`.
+   * @property {((progress: { loaded: number, total: number | null }) => void) | null} onUploadProgress NOTE: non standard extension.
    */
 
   const defaultInnerRequest = {
@@ -71,6 +72,7 @@
     redirectMode: "follow",
     redirectCount: 0,
     clientRid: null,
+    onUploadProgress: null,
   };
 
   /**
@@ -118,6 +120,7 @@
       redirectCount: request.redirectCount,
       urlList: request.urlList,
       clientRid: request.clientRid,
+      onUploadProgress: request.onUploadProgress,
     };
   }
 
@@ -272,6 +275,12 @@
         request.clientRid = init.client?.rid ?? null;
       }
 
+      // NOTE: non standard extension. Observes how much of the request body
+      // has been handed to the network.
+      if (init.onUploadProgress !== undefined) {
+        request.onUploadProgress = init.onUploadProgress;
+      }
+
       // 27.
       this[_request] = request;
 
@@ -452,6 +461,12 @@
         ),
       },
       { key: "client", converter: webidl.converters.any },
+      {
+        key: "onUploadProgress",
+        converter: webidl.createNullableConverter(
+          webidl.converters.Function,
+        ),
+      },
     ],
   );
 
@@ -41,6 +41,10 @@
     Uint8Array,
   } = window.__bootstrap.primordials;
 
+  // Static bodies are split into chunks of this size when upload progress is
+  // being observed, so that progress can be reported while they are sent.
+  const UPLOAD_PROGRESS_CHUNK_SIZE = 65536;
+
   const REQUEST_BODY_HEADER_NAMES = [
     "content-encoding",
     "content-language",
@@ -104,6 +108,7 @@
     }
     // TODO(lucacasonato): clean up registration
     terminator[abortSignal.add](onAbort);
+
     const readable = new ReadableStream({
       type: "bytes",
       async pull(controller) {
@@ -184,6 +189,38 @@
       }
     }
 
+    if (
+      req.onUploadProgress !== null && reqBody instanceof Uint8Array &&
+      reqBody.byteLength > UPLOAD_PROGRESS_CHUNK_SIZE
+    ) {
+      const body = reqBody;
+      let offset = 0;
+      reqBody = new ReadableStream({
+        pull(controller) {
+          const end = offset + UPLOAD_PROGRESS_CHUNK_SIZE;
+          controller.enqueue(TypedArrayPrototypeSubarray(body, offset, end));
+          offset = end;
+          if (offset >= body.byteLength) controller.close();
+        },
+      });
+    }
+
+    const bodyLength = req.body?.length ?? null;
+    let uploaded = 0;
+    let uploadError = null;
+    function reportUploadProgress(bytes) {
+      uploaded += bytes;
+      if (req.onUploadProgress === null || uploadError !== null) return;
+      try {
+        req.onUploadProgress({ loaded: uploaded, total: bodyLength });
+      } catch (err) {
+        // Abort the request instead of finishing it with a truncated body, and
+        // reject the fetch with the error.
+        uploadError = err;
+        onAbort();
+      }
+    }
+
     const { requestRid, requestBodyRid, cancelHandleRid } = opFetch({
       method: req.method,
       url: req.currentUrl(),
@@ -235,6 +272,11 @@
               },
             );
             if (terminator.aborted) break;
+            reportUploadProgress(value.byteLength);
+            if (uploadError !== null) {
+              await reader.cancel(uploadError);
+              break;
+            }
           } catch (err) {
             await reader.cancel(err);
             break;
@@ -251,7 +293,7 @@
     let resp;
     try {
       resp = await PromisePrototypeCatch(opFetchSend(requestRid), (err) => {
-        if (terminator.aborted) return;
+        if (terminator.aborted || uploadError !== null) return;
         throw err;
       });
     } finally {
@@ -261,6 +303,14 @@
         // might have already been closed
       }
     }
+    // A static body was sent in one go along with the request.
+    if (resp !== undefined && reqBody instanceof Uint8Array) {
+      reportUploadProgress(reqBody.byteLength);
+    }
+    if (uploadError !== null) {
+      if (resp !== undefined) core.close(resp.responseRid);
+      throw uploadError;
+    }
     if (terminator.aborted) return abortedNetworkError();
 
     /** @type {InnerResponse} */
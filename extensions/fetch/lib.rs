@@ -42,9 +42,12 @@ use serde::Deserialize;
 use serde::Serialize;
 use std::borrow::Cow;
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::convert::From;
 use std::fs::File;
 use std::io::Read;
+use std::net::IpAddr;
+use std::net::SocketAddr;
 use std::path::Path;
 use std::path::PathBuf;
 use std::pin::Pin;
@@ -82,8 +85,13 @@ pub fn init<P: FetchPermissions + 'static>(
     ])
     .state(move |state| {
       state.put::<reqwest::Client>({
-        create_http_client(user_agent.clone(), ca_data.clone(), proxy.clone())
-          .unwrap()
+        create_http_client(
+          user_agent.clone(),
+          ca_data.clone(),
+          proxy.clone(),
+          HashMap::new(),
+        )
+        .unwrap()
       });
       state.put::<HttpClientDefaults>(HttpClientDefaults {
         ca_data: ca_data.clone(),
@@ -150,15 +158,15 @@ pub fn op_fetch<FP>(
 where
   FP: FetchPermissions + 'static,
 {
-  let client = if let Some(rid) = args.client_rid {
+  let (client, resolve) = if let Some(rid) = args.client_rid {
     let r = state
       .resource_table
       .get::<HttpClientResource>(rid)
       .ok_or_else(bad_resource_id)?;
-    r.client.clone()
+    (r.client.clone(), r.resolve.clone())
   } else {
     let client = state.borrow::<reqwest::Client>();
-    client.clone()
+    (client.clone(), HashMap::new())
   };
 
   let method = Method::from_bytes(&args.method)?;
@@ -170,6 +178,12 @@ where
     "http" | "https" => {
       let permissions = state.borrow_mut::<FP>();
       permissions.check_net_url(&url)?;
+      // A pinned hostname connects to its address on the port from the URL.
+      if let Some(addr) = url.host_str().and_then(|host| resolve.get(host)) {
+        let mut pinned_url = url.clone();
+        pinned_url.set_ip_host(*addr).unwrap();
+        permissions.check_net_url(&pinned_url)?;
+      }
 
       let mut request = client.request(method, url);
 
@@ -474,6 +488,7 @@ impl Resource for FetchResponseBodyResource {
 
 struct HttpClientResource {
   client: Client,
+  resolve: HashMap<String, IpAddr>,
 }
 
 impl Resource for HttpClientResource {
@@ -483,8 +498,8 @@ impl Resource for HttpClientResource {
 }
 
 impl HttpClientResource {
-  fn new(client: Client) -> Self {
-    Self { client }
+  fn new(client: Client, resolve: HashMap<String, IpAddr>) -> Self {
+    Self { client, resolve }
   }
 }
 
@@ -495,6 +510,7 @@ pub struct CreateHttpClientOptions {
   ca_file: Option<String>,
   ca_data: Option<ByteString>,
   proxy: Option<Proxy>,
+  resolve: HashMap<String, IpAddr>,
 }
 
 #[derive(Deserialize, Default, Debug, Clone)]
@@ -539,10 +555,13 @@ where
     defaults.user_agent.clone(),
     cert_data.or_else(|| defaults.ca_data.clone()),
     args.proxy,
+    args.resolve.clone(),
   )
   .unwrap();
 
-  let rid = state.resource_table.add(HttpClientResource::new(client));
+  let rid = state
+    .resource_table
+    .add(HttpClientResource::new(client, args.resolve));
   Ok(rid)
 }
 
@@ -562,11 +581,13 @@ fn get_cert_data(
 }
 
 /// Create new instance of async reqwest::Client. This client supports
-/// proxies and doesn't follow redirects.
+/// proxies and doesn't follow redirects. Hostnames in `resolve` bypass DNS
+/// and connect to the given address instead.
 pub fn create_http_client(
   user_agent: String,
   ca_data: Option<Vec<u8>>,
   proxy: Option<Proxy>,
+  resolve: HashMap<String, IpAddr>,
 ) -> Result<Client, AnyError> {
   let mut headers = HeaderMap::new();
   headers.insert(USER_AGENT, user_agent.parse().unwrap());
@@ -581,14 +602,31 @@ pub fn create_http_client(
   }
 
   if let Some(proxy) = proxy {
-    let mut reqwest_proxy = reqwest::Proxy::all(&proxy.url)?;
-    if let Some(basic_auth) = &proxy.basic_auth {
-      reqwest_proxy =
-        reqwest_proxy.basic_auth(&basic_auth.username, &basic_auth.password);
-    }
+    let is_socks =
+      proxy.url.starts_with("socks5:") || proxy.url.starts_with("socks5h:");
+    let reqwest_proxy = match &proxy.basic_auth {
+      Some(basic_auth) if is_socks => {
+        // reqwest panics when basic auth is set on a SOCKS proxy, but takes the
+        // credentials from the URL.
+        let mut url = Url::parse(&proxy.url)?;
+        url
+          .set_username(&basic_auth.username)
+          .and_then(|_| url.set_password(Some(&basic_auth.password)))
+          .map_err(|_| type_error("Invalid proxy url"))?;
+        reqwest::Proxy::all(url)?
+      }
+      Some(basic_auth) => reqwest::Proxy::all(&proxy.url)?
+        .basic_auth(&basic_auth.username, &basic_auth.password),
+      None => reqwest::Proxy::all(&proxy.url)?,
+    };
     builder = builder.proxy(reqwest_proxy);
   }
 
+  for (domain, addr) in resolve {
+    // The port is ignored by reqwest; the one from the request URL is used.
+    builder = builder.resolve(&domain, SocketAddr::new(addr, 0));
+  }
+
   builder
     .build()
     .map_err(|e| generic_error(format!("Unable to build http client: {}", e)))
@@ -5,9 +5,12 @@
   const core = window.Deno.core;
   const { BadResource } = core;
   const {
+    ArrayPrototypeMap,
+    ArrayPrototypePush,
     PromiseResolve,
     SymbolAsyncIterator,
     Uint8Array,
+    TypedArrayPrototypeSet,
     TypedArrayPrototypeSubarray,
   } = window.__bootstrap.primordials;
 
@@ -54,6 +57,18 @@
     return core.opAsync("op_datagram_send", args, zeroCopy);
   }
 
+  function opReceiveBatch(rid, transport, count, zeroCopy) {
+    return core.opAsync(
+      "op_datagram_receive_batch",
+      { rid, transport, count },
+      zeroCopy,
+    );
+  }
+
+  function opSendBatch(args, zeroCopy) {
+    return core.opAsync("op_datagram_send_batch", args, zeroCopy);
+  }
+
   function resolveDns(query, recordType, options) {
     return core.opAsync("op_dns_resolve", { query, recordType, options });
   }
@@ -182,6 +197,46 @@
       return opSend(args, p);
     }
 
+    async receiveBatch(count = 16, size = this.bufSize) {
+      const buf = new Uint8Array(count * size);
+      const packets = await opReceiveBatch(
+        this.rid,
+        this.addr.transport,
+        count,
+        buf,
+      );
+      return ArrayPrototypeMap(packets, ({ size: n, remoteAddr }, i) => {
+        const sub = TypedArrayPrototypeSubarray(buf, i * size, i * size + n);
+        return [sub, remoteAddr];
+      });
+    }
+
+    sendBatch(messages) {
+      const lengths = [];
+      const addrs = [];
+      let byteLength = 0;
+      for (const [p, addr] of messages) {
+        ArrayPrototypePush(lengths, p.byteLength);
+        ArrayPrototypePush(addrs, { hostname: "127.0.0.1", ...addr });
+        byteLength += p.byteLength;
+      }
+      // All messages go to the op in one buffer, back to back.
+      const buf = new Uint8Array(byteLength);
+      let offset = 0;
+      for (const [p] of messages) {
+        TypedArrayPrototypeSet(buf, p, offset);
+        offset += p.byteLength;
+      }
+
+      const args = {
+        rid: this.rid,
+        transport: this.addr.transport,
+        lengths,
+        addrs,
+      };
+      return opSendBatch(args, buf);
+    }
+
     close() {
       core.close(this.rid);
     }
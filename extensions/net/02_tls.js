@@ -52,6 +52,7 @@
     hostname = "0.0.0.0",
     transport = "tcp",
     alpnProtocols,
+    reusePort = false,
   }) {
     const res = opListenTls({
       port,
@@ -60,6 +61,7 @@
       hostname,
       transport,
       alpnProtocols,
+      reusePort,
     });
     return new TLSListener(res.rid, res.localAddr);
   }
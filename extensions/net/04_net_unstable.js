@@ -4,10 +4,16 @@
 ((window) => {
   const net = window.__bootstrap.net;
 
+  // Listen on every context ID the host has by default.
+  const VMADDR_CID_ANY = 0xFFFFFFFF;
+
   function listen(options) {
     if (options.transport === "unix") {
       const res = net.opListen(options);
       return new net.Listener(res.rid, res.localAddr);
+    } else if (options.transport === "vsock") {
+      const res = net.opListen({ cid: VMADDR_CID_ANY, ...options });
+      return new net.Listener(res.rid, res.localAddr);
     } else {
       return net.listen(options);
     }
@@ -33,7 +39,7 @@
   async function connect(
     options,
   ) {
-    if (options.transport === "unix") {
+    if (options.transport === "unix" || options.transport === "vsock") {
       const res = await net.opConnect(options);
       return new net.Conn(res.rid, res.remoteAddr, res.localAddr);
     } else {
@@ -27,6 +27,13 @@ use tokio::net::tcp;
 #[cfg(unix)]
 use tokio::net::unix;
 
+#[cfg(target_os = "linux")]
+use crate::ops_vsock::VsockStream;
+#[cfg(target_os = "linux")]
+use tokio::io::ReadHalf;
+#[cfg(target_os = "linux")]
+use tokio::io::WriteHalf;
+
 pub fn init() -> Vec<OpPair> {
   vec![
     ("op_net_read_async", op_async(op_read_async)),
@@ -163,6 +170,42 @@ impl Resource for UnixStreamResource {
   }
 }
 
+#[cfg(target_os = "linux")]
+pub type VsockStreamResource =
+  FullDuplexResource<ReadHalf<VsockStream>, WriteHalf<VsockStream>>;
+
+#[cfg(not(target_os = "linux"))]
+pub struct VsockStreamResource;
+
+#[cfg(not(target_os = "linux"))]
+impl VsockStreamResource {
+  pub async fn read(
+    self: &Rc<Self>,
+    _buf: &mut [u8],
+  ) -> Result<usize, AnyError> {
+    unreachable!()
+  }
+  pub async fn write(self: &Rc<Self>, _buf: &[u8]) -> Result<usize, AnyError> {
+    unreachable!()
+  }
+  pub async fn shutdown(self: &Rc<Self>) -> Result<(), AnyError> {
+    unreachable!()
+  }
+  pub fn cancel_read_ops(&self) {
+    unreachable!()
+  }
+}
+
+impl Resource for VsockStreamResource {
+  fn name(&self) -> Cow<str> {
+    "vsockStream".into()
+  }
+
+  fn close(self: Rc<Self>) {
+    self.cancel_read_ops();
+  }
+}
+
 async fn op_read_async(
   state: Rc<RefCell<OpState>>,
   rid: ResourceId,
@@ -180,6 +223,8 @@ async fn op_read_async(
     s.read(buf).await?
   } else if let Some(s) = resource.downcast_rc::<UnixStreamResource>() {
     s.read(buf).await?
+  } else if let Some(s) = resource.downcast_rc::<VsockStreamResource>() {
+    s.read(buf).await?
   } else {
     return Err(not_supported());
   };
@@ -203,6 +248,8 @@ async fn op_write_async(
     s.write(buf).await?
   } else if let Some(s) = resource.downcast_rc::<UnixStreamResource>() {
     s.write(buf).await?
+  } else if let Some(s) = resource.downcast_rc::<VsockStreamResource>() {
+    s.write(buf).await?
   } else {
     return Err(not_supported());
   };
@@ -225,6 +272,8 @@ async fn op_shutdown(
     s.shutdown().await?;
   } else if let Some(s) = resource.downcast_rc::<UnixStreamResource>() {
     s.shutdown().await?;
+  } else if let Some(s) = resource.downcast_rc::<VsockStreamResource>() {
+    s.shutdown().await?;
   } else {
     return Err(not_supported());
   }
@@ -15,7 +15,16 @@ declare namespace Deno {
     path: string;
   }
 
-  export type Addr = NetAddr | UnixAddr;
+  /** **UNSTABLE**: The vsock transport is a new API yet to be vetted.
+   *
+   * The address of either end of a vsock `Conn`, and of a vsock `Listener`. */
+  export interface VsockAddr {
+    transport: "vsock";
+    cid: number;
+    port: number;
+  }
+
+  export type Addr = NetAddr | UnixAddr | VsockAddr;
 
   /** A generic network listener for stream-oriented protocols. */
   export interface Listener extends AsyncIterable<Conn> {
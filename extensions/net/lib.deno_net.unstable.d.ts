@@ -104,6 +104,22 @@ declare namespace Deno {
     send(p: Uint8Array, addr: Addr): Promise<number>;
     /** UNSTABLE: new API, yet to be vetted.
   *
+  * Waits for at least one message and resolves to all the messages that
+  * arrived, up to `count` (16 by default) of them. Each message is received
+  * into a buffer of `size` bytes, which defaults to 1024. Only supported for
+  * `udp`. */
+    receiveBatch(count?: number, size?: number): Promise<[Uint8Array, Addr][]>;
+    /** UNSTABLE: new API, yet to be vetted.
+  *
+  * Sends several messages, each to its own target, and resolves to the number
+  * of messages sent. This is less than `messages.length` if sending failed
+  * partway through; sending the rest again reports the error. On Linux the
+  * messages go out with a single system call and consecutive messages of the
+  * same size to the same target are handed to the network device as one.
+  * Only supported for `udp`. */
+    sendBatch(messages: [Uint8Array, Addr][]): Promise<number>;
+    /** UNSTABLE: new API, yet to be vetted.
+  *
   * Close closes the socket. Any pending message promises will be rejected
   * with errors. */
     close(): void;
@@ -130,6 +146,42 @@ declare namespace Deno {
     options: UnixListenOptions & { transport: "unix" },
   ): Listener;
 
+  export interface ListenOptions {
+    /** **UNSTABLE**: new option, yet to be vetted.
+     *
+     * Allow other listeners that set this option to bind the same address and
+     * port, so that several processes can accept connections on it; the
+     * kernel balances incoming connections between them. Only supported for
+     * TCP, and not supported on Windows. Defaults to `false`. */
+    reusePort?: boolean;
+  }
+
+  export interface VsockListenOptions {
+    /** The context ID to listen on. If not specified, defaults to any
+     * context ID of this host. */
+    cid?: number;
+    /** The port to listen on. */
+    port: number;
+  }
+
+  /** **UNSTABLE**: new API, yet to be vetted.
+*
+* Listen announces on the local vsock address. vsock connects virtual
+* machines and enclaves to their hypervisor without a network interface;
+* addresses are a context ID (CID) and a port.
+*
+* ```ts
+* const listener = Deno.listen({ port: 5000, transport: "vsock" })
+* ```
+*
+* Only supported on Linux. Requires `allow-net` permission; vsock addresses
+* can be allowed with `--allow-net=vsock`, `--allow-net=vsock:<cid>` or
+* `--allow-net=vsock:<cid>:<port>`. A listener that doesn't set `cid` listens
+* on context ID 4294967295 (any). */
+  export function listen(
+    options: VsockListenOptions & { transport: "vsock" },
+  ): Listener;
+
   /** **UNSTABLE**: new API, yet to be vetted
 *
 * Listen announces on the local transport address.
@@ -172,6 +224,14 @@ declare namespace Deno {
     path: string;
   }
 
+  export interface VsockConnectOptions {
+    transport: "vsock";
+    /** The context ID of the peer, e.g. `2` for the host of a virtual
+     * machine. */
+    cid: number;
+    port: number;
+  }
+
   /** **UNSTABLE**:  The unix socket transport is unstable as a new API yet to
 * be vetted.  The TCP transport is considered stable.
 *
@@ -184,11 +244,13 @@ declare namespace Deno {
 * const conn3 = await Deno.connect({ hostname: "[2001:db8::1]", port: 80 });
 * const conn4 = await Deno.connect({ hostname: "golang.org", port: 80, transport: "tcp" });
 * const conn5 = await Deno.connect({ path: "/foo/bar.sock", transport: "unix" });
+* const conn6 = await Deno.connect({ cid: 2, port: 5000, transport: "vsock" });
 * ```
 *
-* Requires `allow-net` permission for "tcp" and `allow-read` for "unix". */
+* Requires `allow-net` permission for "tcp" and "vsock", and `allow-read` for
+* "unix". The "vsock" transport is only supported on Linux. */
   export function connect(
-    options: ConnectOptions | UnixConnectOptions,
+    options: ConnectOptions | UnixConnectOptions | VsockConnectOptions,
   ): Promise<Conn>;
 
   export interface StartTlsOptions {
@@ -3,8 +3,11 @@
 pub mod io;
 pub mod ops;
 pub mod ops_tls;
+pub mod ops_udp;
 #[cfg(unix)]
 pub mod ops_unix;
+#[cfg(target_os = "linux")]
+pub mod ops_vsock;
 pub mod resolve_addr;
 
 use deno_core::error::AnyError;
@@ -12,6 +15,7 @@ use deno_core::include_js_files;
 use deno_core::Extension;
 use deno_core::OpState;
 use std::cell::RefCell;
+use std::net::SocketAddr;
 use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
@@ -21,6 +25,23 @@ pub trait NetPermissions {
     &mut self,
     _host: &(T, Option<u16>),
   ) -> Result<(), AnyError>;
+  /// Returns true if access to `host` can't be decided by its name alone, and
+  /// `check_net_resolved` should be called instead of `check_net` once it has
+  /// been resolved.
+  fn needs_resolution<T: AsRef<str>>(&self, _host: &(T, Option<u16>)) -> bool {
+    false
+  }
+  /// Checks access to `host` once it has been resolved to `addr`, so that
+  /// permissions scoped to IP ranges can be applied to the actual address.
+  fn check_net_resolved<T: AsRef<str>>(
+    &mut self,
+    host: &(T, Option<u16>),
+    _addr: &SocketAddr,
+  ) -> Result<(), AnyError> {
+    self.check_net(host)
+  }
+  /// Checks access to the vsock address with context ID `cid` and `port`.
+  fn check_vsock(&mut self, _cid: u32, _port: u32) -> Result<(), AnyError>;
   fn check_read(&mut self, _p: &Path) -> Result<(), AnyError>;
   fn check_write(&mut self, _p: &Path) -> Result<(), AnyError>;
 }
@@ -36,6 +57,10 @@ impl NetPermissions for NoNetPermissions {
     Ok(())
   }
 
+  fn check_vsock(&mut self, _cid: u32, _port: u32) -> Result<(), AnyError> {
+    Ok(())
+  }
+
   fn check_read(&mut self, _p: &Path) -> Result<(), AnyError> {
     Ok(())
   }
@@ -93,6 +118,7 @@ pub fn init<P: NetPermissions + 'static>(unstable: bool) -> Extension {
   ops_to_register.extend(io::init());
   ops_to_register.extend(ops::init::<P>());
   ops_to_register.extend(ops_tls::init::<P>());
+  ops_to_register.extend(ops_udp::init::<P>());
 
   Extension::builder()
     .js(include_js_files!(
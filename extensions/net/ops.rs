@@ -45,6 +45,22 @@ use super::ops_unix as net_unix;
 use crate::io::UnixStreamResource;
 #[cfg(unix)]
 use std::path::Path;
+#[cfg(unix)]
+use tokio::net::TcpSocket;
+
+#[cfg(not(unix))]
+use deno_core::error::not_supported;
+
+#[cfg(target_os = "linux")]
+use super::ops_vsock as net_vsock;
+#[cfg(target_os = "linux")]
+use crate::io::VsockStreamResource;
+#[cfg(target_os = "linux")]
+use crate::ops_udp::sys as udp_sys;
+#[cfg(target_os = "linux")]
+use std::cell::Cell;
+#[cfg(target_os = "linux")]
+use std::os::unix::io::AsRawFd;
 
 pub fn init<P: NetPermissions + 'static>() -> Vec<OpPair> {
   vec![
@@ -74,6 +90,8 @@ pub enum OpAddr {
   Unix(net_unix::UnixAddr),
   #[cfg(unix)]
   UnixPacket(net_unix::UnixAddr),
+  #[cfg(target_os = "linux")]
+  Vsock(net_vsock::VsockAddr),
 }
 
 #[derive(Serialize)]
@@ -150,14 +168,48 @@ async fn op_accept(
     "tcp" => accept_tcp(state, args, ()).await,
     #[cfg(unix)]
     "unix" => net_unix::accept_unix(state, args, ()).await,
+    #[cfg(target_os = "linux")]
+    "vsock" => net_vsock::accept_vsock(state, args, ()).await,
     other => Err(bad_transport(other)),
   }
 }
 
-fn bad_transport(transport: &str) -> AnyError {
+pub(crate) fn bad_transport(transport: &str) -> AnyError {
   generic_error(format!("Unsupported transport protocol {}", transport))
 }
 
+/// Checks access to `hostname:port` and resolves it. The hostname is only
+/// looked up before the check when nothing but a CIDR range could allow it, so
+/// that scripts without net permission can't use DNS queries to leak data.
+pub(crate) async fn resolve_checked<NP>(
+  state: &Rc<RefCell<OpState>>,
+  hostname: &str,
+  port: u16,
+) -> Result<SocketAddr, AnyError>
+where
+  NP: NetPermissions + 'static,
+{
+  let host = &(hostname, Some(port));
+  let needs_resolution = {
+    let mut s = state.borrow_mut();
+    let permissions = s.borrow_mut::<NP>();
+    let needs_resolution = permissions.needs_resolution(host);
+    if !needs_resolution {
+      permissions.check_net(host)?;
+    }
+    needs_resolution
+  };
+  let addr = resolve_addr(hostname, port)
+    .await?
+    .next()
+    .ok_or_else(|| generic_error("No resolved address found"))?;
+  if needs_resolution {
+    let mut s = state.borrow_mut();
+    s.borrow_mut::<NP>().check_net_resolved(host, &addr)?;
+  }
+  Ok(addr)
+}
+
 #[derive(Deserialize)]
 pub(crate) struct ReceiveArgs {
   pub rid: ResourceId,
@@ -232,15 +284,8 @@ where
       transport,
       transport_args: ArgsEnum::Ip(args),
     } if transport == "udp" => {
-      {
-        let mut s = state.borrow_mut();
-        s.borrow_mut::<NP>()
-          .check_net(&(&args.hostname, Some(args.port)))?;
-      }
-      let addr = resolve_addr(&args.hostname, args.port)
-        .await?
-        .next()
-        .ok_or_else(|| generic_error("No resolved address found"))?;
+      let addr =
+        resolve_checked::<NP>(&state, &args.hostname, args.port).await?;
 
       let resource = state
         .borrow_mut()
@@ -299,16 +344,8 @@ where
       transport,
       transport_args: ArgsEnum::Ip(args),
     } if transport == "tcp" => {
-      {
-        let mut state_ = state.borrow_mut();
-        state_
-          .borrow_mut::<NP>()
-          .check_net(&(&args.hostname, Some(args.port)))?;
-      }
-      let addr = resolve_addr(&args.hostname, args.port)
-        .await?
-        .next()
-        .ok_or_else(|| generic_error("No resolved address found"))?;
+      let addr =
+        resolve_checked::<NP>(&state, &args.hostname, args.port).await?;
       let tcp_stream = TcpStream::connect(&addr).await?;
       let local_addr = tcp_stream.local_addr()?;
       let remote_addr = tcp_stream.peer_addr()?;
@@ -359,6 +396,29 @@ where
         })),
       })
     }
+    #[cfg(target_os = "linux")]
+    ConnectArgs {
+      transport,
+      transport_args: ArgsEnum::Vsock(args),
+    } if transport == "vsock" => {
+      super::check_unstable2(&state, "Deno.connect");
+      {
+        let mut state_ = state.borrow_mut();
+        state_.borrow_mut::<NP>().check_vsock(args.cid, args.port)?;
+      }
+      let stream = net_vsock::VsockStream::connect(args.cid, args.port).await?;
+      let local_addr = stream.local_addr()?;
+      let remote_addr = stream.peer_addr()?;
+
+      let mut state_ = state.borrow_mut();
+      let resource = VsockStreamResource::new(tokio::io::split(stream));
+      let rid = state_.resource_table.add(resource);
+      Ok(OpConn {
+        rid,
+        local_addr: Some(OpAddr::Vsock(local_addr)),
+        remote_addr: Some(OpAddr::Vsock(remote_addr)),
+      })
+    }
     _ => Err(type_error("Wrong argument format!")),
   }
 }
@@ -378,9 +438,12 @@ impl Resource for TcpListenerResource {
   }
 }
 
-struct UdpSocketResource {
-  socket: AsyncRefCell<UdpSocket>,
-  cancel: CancelHandle,
+pub(crate) struct UdpSocketResource {
+  pub socket: AsyncRefCell<UdpSocket>,
+  pub cancel: CancelHandle,
+  /// Whether batched sends may use UDP generic segmentation offload.
+  #[cfg(target_os = "linux")]
+  pub gso: Cell<bool>,
 }
 
 impl Resource for UdpSocketResource {
@@ -394,9 +457,12 @@ impl Resource for UdpSocketResource {
 }
 
 #[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct IpListenArgs {
   hostname: String,
   port: u16,
+  #[serde(default)]
+  reuse_port: bool,
 }
 
 #[derive(Deserialize)]
@@ -405,6 +471,8 @@ enum ArgsEnum {
   Ip(IpListenArgs),
   #[cfg(unix)]
   Unix(net_unix::UnixListenArgs),
+  #[cfg(target_os = "linux")]
+  Vsock(net_vsock::VsockListenArgs),
 }
 
 #[derive(Deserialize)]
@@ -414,13 +482,48 @@ struct ListenArgs {
   transport_args: ArgsEnum,
 }
 
-fn listen_tcp(
-  state: &mut OpState,
+/// Binds a TCP listener to `addr`. With `reuse_port` set, other listeners
+/// that set it too can bind the same address, and the kernel balances incoming
+/// connections between them.
+pub(crate) fn bind_tcp(
   addr: SocketAddr,
-) -> Result<(u32, SocketAddr), AnyError> {
+  reuse_port: bool,
+) -> Result<TcpListener, AnyError> {
+  if reuse_port {
+    return bind_tcp_reuse_port(addr);
+  }
   let std_listener = std::net::TcpListener::bind(&addr)?;
   std_listener.set_nonblocking(true)?;
   let listener = TcpListener::from_std(std_listener)?;
+  Ok(listener)
+}
+
+#[cfg(unix)]
+fn bind_tcp_reuse_port(addr: SocketAddr) -> Result<TcpListener, AnyError> {
+  let socket = if addr.is_ipv4() {
+    TcpSocket::new_v4()?
+  } else {
+    TcpSocket::new_v6()?
+  };
+  // The standard library sets SO_REUSEADDR on listeners as well.
+  socket.set_reuseaddr(true)?;
+  socket.set_reuseport(true)?;
+  socket.bind(addr)?;
+  let listener = socket.listen(128)?;
+  Ok(listener)
+}
+
+#[cfg(not(unix))]
+fn bind_tcp_reuse_port(_addr: SocketAddr) -> Result<TcpListener, AnyError> {
+  Err(not_supported())
+}
+
+fn listen_tcp(
+  state: &mut OpState,
+  addr: SocketAddr,
+  reuse_port: bool,
+) -> Result<(u32, SocketAddr), AnyError> {
+  let listener = bind_tcp(addr, reuse_port)?;
   let local_addr = listener.local_addr()?;
   let listener_resource = TcpListenerResource {
     listener: AsyncRefCell::new(listener),
@@ -440,6 +543,8 @@ fn listen_udp(
   let socket = UdpSocket::from_std(std_socket)?;
   let local_addr = socket.local_addr()?;
   let socket_resource = UdpSocketResource {
+    #[cfg(target_os = "linux")]
+    gso: Cell::new(udp_sys::gso_supported(socket.as_raw_fd())),
     socket: AsyncRefCell::new(socket),
     cancel: Default::default(),
   };
@@ -465,6 +570,12 @@ where
         if transport == "udp" {
           super::check_unstable(state, "Deno.listenDatagram");
         }
+        if args.reuse_port {
+          super::check_unstable(state, "Deno.listen#reusePort");
+          if transport != "tcp" {
+            return Err(type_error("reusePort is only supported for TCP"));
+          }
+        }
         state
           .borrow_mut::<NP>()
           .check_net(&(&args.hostname, Some(args.port)))?;
@@ -473,7 +584,7 @@ where
         .next()
         .ok_or_else(|| generic_error("No resolved address found"))?;
       let (rid, local_addr) = if transport == "tcp" {
-        listen_tcp(state, addr)?
+        listen_tcp(state, addr, args.reuse_port)?
       } else {
         listen_udp(state, addr)?
       };
@@ -511,6 +622,9 @@ where
         if transport == "unixpacket" {
           super::check_unstable(state, "Deno.listenDatagram");
         }
+        if args.reuse_port {
+          return Err(type_error("reusePort is only supported for TCP"));
+        }
         let permissions = state.borrow_mut::<NP>();
         permissions.check_read(&address_path)?;
         permissions.check_write(&address_path)?;
@@ -539,6 +653,25 @@ where
         remote_addr: None,
       })
     }
+    #[cfg(target_os = "linux")]
+    ListenArgs {
+      transport,
+      transport_args: ArgsEnum::Vsock(args),
+    } if transport == "vsock" => {
+      super::check_unstable(state, "Deno.listen");
+      state.borrow_mut::<NP>().check_vsock(args.cid, args.port)?;
+      let (rid, local_addr) =
+        net_vsock::listen_vsock(state, args.cid, args.port)?;
+      debug!(
+        "New listener {} vsock {}:{}",
+        rid, local_addr.cid, local_addr.port
+      );
+      Ok(OpConn {
+        rid,
+        local_addr: Some(OpAddr::Vsock(local_addr)),
+        remote_addr: None,
+      })
+    }
     #[cfg(unix)]
     _ => Err(type_error("Wrong argument format!")),
   }
@@ -5,10 +5,11 @@ pub use webpki;
 
 use crate::io::TcpStreamResource;
 use crate::io::TlsStreamResource;
+use crate::ops::bind_tcp;
+use crate::ops::resolve_checked;
 use crate::ops::IpAddr;
 use crate::ops::OpAddr;
 use crate::ops::OpConn;
-use crate::resolve_addr::resolve_addr;
 use crate::resolve_addr::resolve_addr_sync;
 use crate::NetPermissions;
 use deno_core::error::bad_resource;
@@ -779,22 +780,14 @@ where
   let port = args.port;
   let cert_file = args.cert_file.as_deref();
 
-  {
-    let mut s = state.borrow_mut();
-    let permissions = s.borrow_mut::<NP>();
-    permissions.check_net(&(hostname, Some(port)))?;
-    if let Some(path) = cert_file {
-      permissions.check_read(Path::new(path))?;
-    }
-  }
-
   let hostname_dns = DNSNameRef::try_from_ascii_str(hostname)
     .map_err(|_| invalid_hostname(hostname))?;
 
-  let connect_addr = resolve_addr(hostname, port)
-    .await?
-    .next()
-    .ok_or_else(|| generic_error("No resolved address found"))?;
+  let connect_addr = resolve_checked::<NP>(&state, hostname, port).await?;
+  if let Some(path) = cert_file {
+    let mut s = state.borrow_mut();
+    s.borrow_mut::<NP>().check_read(Path::new(path))?;
+  }
   let tcp_stream = TcpStream::connect(connect_addr).await?;
   let local_addr = tcp_stream.local_addr()?;
   let remote_addr = tcp_stream.peer_addr()?;
@@ -913,6 +906,8 @@ pub struct ListenTlsArgs {
   cert_file: String,
   key_file: String,
   alpn_protocols: Option<Vec<String>>,
+  #[serde(default)]
+  reuse_port: bool,
 }
 
 fn op_listen_tls<NP>(
@@ -949,9 +944,10 @@ where
   let bind_addr = resolve_addr_sync(hostname, port)?
     .next()
     .ok_or_else(|| generic_error("No resolved address found"))?;
-  let std_listener = std::net::TcpListener::bind(bind_addr)?;
-  std_listener.set_nonblocking(true)?;
-  let tcp_listener = TcpListener::from_std(std_listener)?;
+  if args.reuse_port {
+    super::check_unstable(state, "Deno.listenTls#reusePort");
+  }
+  let tcp_listener = bind_tcp(bind_addr, args.reuse_port)?;
   let local_addr = tcp_listener.local_addr()?;
 
   let tls_listener_resource = TlsListenerResource {
@@ -0,0 +1,459 @@
+// Copyright 2018-2021 the Deno authors. All rights reserved. MIT license.
+
+//! Sending and receiving many UDP datagrams with a single op, so that busy
+//! servers don't pay the op overhead for every packet.
+
+use crate::ops::bad_transport;
+use crate::ops::resolve_checked;
+use crate::ops::IpAddr;
+use crate::ops::OpAddr;
+use crate::ops::OpPacket;
+use crate::ops::UdpSocketResource;
+use crate::NetPermissions;
+use deno_core::error::bad_resource;
+use deno_core::error::null_opbuf;
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+use deno_core::op_async;
+use deno_core::CancelTryFuture;
+use deno_core::OpPair;
+use deno_core::OpState;
+use deno_core::RcRef;
+use deno_core::ResourceId;
+use deno_core::ZeroCopyBuf;
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::rc::Rc;
+use tokio::net::UdpSocket;
+
+pub fn init<P: NetPermissions + 'static>() -> Vec<OpPair> {
+  vec![
+    (
+      "op_datagram_send_batch",
+      op_async(op_datagram_send_batch::<P>),
+    ),
+    (
+      "op_datagram_receive_batch",
+      op_async(op_datagram_receive_batch),
+    ),
+  ]
+}
+
+#[derive(Deserialize)]
+struct BatchAddr {
+  hostname: String,
+  port: u16,
+}
+
+#[derive(Deserialize)]
+struct SendBatchArgs {
+  rid: ResourceId,
+  transport: String,
+  /// The length of each message in the buffer, which holds all of them back
+  /// to back.
+  lengths: Vec<usize>,
+  addrs: Vec<BatchAddr>,
+}
+
+async fn op_datagram_send_batch<NP>(
+  state: Rc<RefCell<OpState>>,
+  args: SendBatchArgs,
+  zero_copy: Option<ZeroCopyBuf>,
+) -> Result<usize, AnyError>
+where
+  NP: NetPermissions + 'static,
+{
+  if args.transport != "udp" {
+    return Err(bad_transport(&args.transport));
+  }
+  let zero_copy = zero_copy.ok_or_else(null_opbuf)?;
+  if args.lengths.len() != args.addrs.len()
+    || args.lengths.iter().sum::<usize>() != zero_copy.len()
+  {
+    return Err(type_error("Message lengths do not match the data"));
+  }
+
+  // Batches usually go to a handful of peers, so check and resolve each
+  // address only once.
+  let mut resolved = HashMap::new();
+  let mut addrs = Vec::with_capacity(args.addrs.len());
+  for BatchAddr { hostname, port } in args.addrs {
+    let key = (hostname, port);
+    if let Some(addr) = resolved.get(&key) {
+      addrs.push(*addr);
+      continue;
+    }
+    let addr = resolve_checked::<NP>(&state, &key.0, port).await?;
+    resolved.insert(key, addr);
+    addrs.push(addr);
+  }
+
+  let mut offset = 0;
+  let messages: Vec<(&[u8], SocketAddr)> = args
+    .lengths
+    .iter()
+    .zip(addrs)
+    .map(|(len, addr)| {
+      let message = &zero_copy[offset..offset + len];
+      offset += len;
+      (message, addr)
+    })
+    .collect();
+
+  let resource = state
+    .borrow_mut()
+    .resource_table
+    .get::<UdpSocketResource>(args.rid)
+    .ok_or_else(|| bad_resource("Socket has been closed"))?;
+  let socket = RcRef::map(&resource, |r| &r.socket).borrow().await;
+  // Like a short write, a batch that fails partway resolves to how much of it
+  // was sent. Sending the rest reports the error.
+  let mut sent = 0;
+  if let Err(err) = send_batch(&resource, &socket, &messages, &mut sent).await {
+    if sent == 0 {
+      return Err(err);
+    }
+  }
+  Ok(sent)
+}
+
+#[derive(Deserialize)]
+struct ReceiveBatchArgs {
+  rid: ResourceId,
+  transport: String,
+  /// The most messages to receive. The buffer is split into this many equal
+  /// parts, one per message.
+  count: usize,
+}
+
+async fn op_datagram_receive_batch(
+  state: Rc<RefCell<OpState>>,
+  args: ReceiveBatchArgs,
+  zero_copy: Option<ZeroCopyBuf>,
+) -> Result<Vec<OpPacket>, AnyError> {
+  if args.transport != "udp" {
+    return Err(bad_transport(&args.transport));
+  }
+  let mut zero_copy = zero_copy.ok_or_else(null_opbuf)?;
+  if args.count == 0
+    || zero_copy.is_empty()
+    || zero_copy.len() % args.count != 0
+  {
+    return Err(type_error("Buffer size must be a multiple of the count"));
+  }
+  let size = zero_copy.len() / args.count;
+  let mut buffers: Vec<&mut [u8]> = zero_copy.chunks_mut(size).collect();
+
+  let resource = state
+    .borrow_mut()
+    .resource_table
+    .get::<UdpSocketResource>(args.rid)
+    .ok_or_else(|| bad_resource("Socket has been closed"))?;
+  let socket = RcRef::map(&resource, |r| &r.socket).borrow().await;
+  let cancel_handle = RcRef::map(&resource, |r| &r.cancel);
+
+  // Wait for the first datagram through tokio so that it keeps track of the
+  // socket's readiness, then take whatever else is already queued.
+  let (first, rest) = buffers.split_first_mut().unwrap();
+  let first = socket.recv_from(first).try_or_cancel(cancel_handle).await?;
+  let mut received = vec![first];
+  received.extend(receive_queued(&socket, rest)?);
+
+  let packets = received
+    .into_iter()
+    .map(|(size, remote_addr)| OpPacket {
+      size,
+      remote_addr: OpAddr::Udp(IpAddr {
+        hostname: remote_addr.ip().to_string(),
+        port: remote_addr.port(),
+      }),
+    })
+    .collect();
+  Ok(packets)
+}
+
+#[cfg(target_os = "linux")]
+async fn send_batch(
+  resource: &UdpSocketResource,
+  socket: &UdpSocket,
+  messages: &[(&[u8], SocketAddr)],
+  sent: &mut usize,
+) -> Result<(), AnyError> {
+  use nix::errno::Errno;
+  use std::os::unix::io::AsRawFd;
+
+  while *sent < messages.len() {
+    let gso = resource.gso.get();
+    match sys::send(socket.as_raw_fd(), &messages[*sent..], gso) {
+      Ok(n) => *sent += n,
+      Err(nix::Error::Sys(Errno::EAGAIN)) => {
+        // Let tokio wait for the socket to become writable again.
+        let (message, addr) = messages[*sent];
+        socket.send_to(message, addr).await?;
+        *sent += 1;
+      }
+      // The network device can't segment the datagrams for us.
+      Err(nix::Error::Sys(Errno::EIO)) if gso => resource.gso.set(false),
+      Err(err) => return Err(sys::to_io_error(err).into()),
+    }
+  }
+  Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+async fn send_batch(
+  _resource: &UdpSocketResource,
+  socket: &UdpSocket,
+  messages: &[(&[u8], SocketAddr)],
+  sent: &mut usize,
+) -> Result<(), AnyError> {
+  for (message, addr) in messages {
+    socket.send_to(message, addr).await?;
+    *sent += 1;
+  }
+  Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn receive_queued(
+  socket: &UdpSocket,
+  buffers: &mut [&mut [u8]],
+) -> Result<Vec<(usize, SocketAddr)>, AnyError> {
+  use std::os::unix::io::AsRawFd;
+
+  if buffers.is_empty() {
+    return Ok(vec![]);
+  }
+  sys::receive(socket.as_raw_fd(), buffers)
+    .map_err(|err| sys::to_io_error(err).into())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn receive_queued(
+  socket: &UdpSocket,
+  buffers: &mut [&mut [u8]],
+) -> Result<Vec<(usize, SocketAddr)>, AnyError> {
+  let mut received = vec![];
+  for buf in buffers {
+    match socket.try_recv_from(buf) {
+      Ok(packet) => received.push(packet),
+      Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => break,
+      Err(err) => return Err(err.into()),
+    }
+  }
+  Ok(received)
+}
+
+/// `sendmmsg(2)` and `recvmmsg(2)`, with UDP generic segmentation offload
+/// where the kernel supports it.
+#[cfg(target_os = "linux")]
+pub(crate) mod sys {
+  use nix::errno::Errno;
+  use nix::libc;
+  use nix::sys::socket;
+  use nix::sys::socket::sockopt;
+  use nix::sys::socket::InetAddr;
+  use nix::sys::socket::MsgFlags;
+  use nix::sys::socket::RecvMmsgData;
+  use nix::sys::socket::SockAddr;
+  use nix::sys::uio::IoVec;
+  use std::io;
+  use std::mem;
+  use std::mem::size_of;
+  use std::net::SocketAddr;
+  use std::ops::Range;
+  use std::os::unix::io::RawFd;
+  use std::ptr;
+
+  /// The most segments the kernel accepts in one offloaded datagram.
+  const MAX_SEGMENTS: usize = 64;
+  /// The most data one offloaded datagram can carry.
+  const MAX_DATAGRAM_SIZE: usize = 65507;
+
+  pub fn to_io_error(err: nix::Error) -> io::Error {
+    match err {
+      nix::Error::Sys(errno) => io::Error::from_raw_os_error(errno as i32),
+      other => io::Error::new(io::ErrorKind::Other, other),
+    }
+  }
+
+  /// Whether the kernel can split one large datagram into several for us.
+  pub fn gso_supported(fd: RawFd) -> bool {
+    socket::getsockopt(fd, sockopt::UdpGsoSegment).is_ok()
+  }
+
+  /// Splits `messages` into runs that can each go out as a single offloaded
+  /// datagram: consecutive messages to the same address with the same size,
+  /// except for the last which may be shorter.
+  fn runs(messages: &[(&[u8], SocketAddr)], gso: bool) -> Vec<Range<usize>> {
+    let mut runs = vec![];
+    let mut start = 0;
+    while start < messages.len() {
+      let (first, addr) = messages[start];
+      let mut end = start + 1;
+      let mut total = first.len();
+      while gso && !first.is_empty() && end < messages.len() {
+        let (message, next_addr) = messages[end];
+        if next_addr != addr
+          || message.is_empty()
+          || message.len() > first.len()
+          || total + message.len() > MAX_DATAGRAM_SIZE
+          || end - start == MAX_SEGMENTS
+        {
+          break;
+        }
+        total += message.len();
+        end += 1;
+        if message.len() < first.len() {
+          break;
+        }
+      }
+      runs.push(start..end);
+      start = end;
+    }
+    runs
+  }
+
+  /// Sends as many of `messages` as the socket takes without blocking, and
+  /// returns how many that was.
+  ///
+  /// This calls `sendmmsg(2)` directly: nix's wrapper reports every message
+  /// as sent even when the kernel took fewer, and may move its control message
+  /// buffer while earlier headers still point into it.
+  pub fn send(
+    fd: RawFd,
+    messages: &[(&[u8], SocketAddr)],
+    gso: bool,
+  ) -> nix::Result<usize> {
+    let runs = runs(messages, gso);
+    let addrs: Vec<SockAddr> = runs
+      .iter()
+      .map(|run| SockAddr::new_inet(InetAddr::from_std(&messages[run.start].1)))
+      .collect();
+    let iovs: Vec<libc::iovec> = messages
+      .iter()
+      .map(|(message, _)| libc::iovec {
+        iov_base: message.as_ptr() as *mut libc::c_void,
+        iov_len: message.len(),
+      })
+      .collect();
+
+    // One `UDP_SEGMENT` control message per run, allocated up front so that
+    // the headers' pointers into it stay valid. `u64` keeps it aligned for
+    // `cmsghdr`.
+    let cmsg_len = unsafe { libc::CMSG_LEN(size_of::<u16>() as _) } as usize;
+    let cmsg_space =
+      unsafe { libc::CMSG_SPACE(size_of::<u16>() as _) } as usize;
+    let mut cmsgs =
+      vec![0u64; (cmsg_space * runs.len() + 7) / size_of::<u64>()];
+
+    let mut headers = Vec::with_capacity(runs.len());
+    for (i, (run, addr)) in runs.iter().zip(&addrs).enumerate() {
+      let (name, name_len) = addr.as_ffi_pair();
+      // SAFETY: all-zero is a valid `msghdr`, and every pointer stored in it
+      // refers to `addrs`, `iovs` or `cmsgs`, which outlive the call below.
+      let mut hdr: libc::msghdr = unsafe { mem::zeroed() };
+      hdr.msg_name = name as *const libc::sockaddr as *mut libc::c_void;
+      hdr.msg_namelen = name_len;
+      hdr.msg_iov = iovs[run.clone()].as_ptr() as *mut libc::iovec;
+      hdr.msg_iovlen = run.len() as _;
+      if run.len() > 1 {
+        let segment_size = messages[run.start].0.len() as u16;
+        unsafe {
+          let control = (cmsgs.as_mut_ptr() as *mut u8).add(i * cmsg_space);
+          hdr.msg_control = control as *mut libc::c_void;
+          hdr.msg_controllen = cmsg_space as _;
+          let cmsg = libc::CMSG_FIRSTHDR(&hdr);
+          (*cmsg).cmsg_level = libc::SOL_UDP;
+          (*cmsg).cmsg_type = libc::UDP_SEGMENT;
+          (*cmsg).cmsg_len = cmsg_len as _;
+          ptr::write_unaligned(libc::CMSG_DATA(cmsg) as *mut u16, segment_size);
+        }
+      }
+      headers.push(libc::mmsghdr {
+        msg_hdr: hdr,
+        msg_len: 0,
+      });
+    }
+
+    let sent = unsafe {
+      libc::sendmmsg(fd, headers.as_mut_ptr(), headers.len() as _, 0)
+    };
+    let sent = Errno::result(sent)? as usize;
+    Ok(runs[..sent].iter().map(|run| run.len()).sum())
+  }
+
+  /// Receives the datagrams that are already queued on the socket, one into
+  /// each of `buffers`, without blocking.
+  pub fn receive(
+    fd: RawFd,
+    buffers: &mut [&mut [u8]],
+  ) -> nix::Result<Vec<(usize, SocketAddr)>> {
+    let mut data: Vec<_> = buffers
+      .iter_mut()
+      .map(|buf| RecvMmsgData {
+        iov: [IoVec::from_mut_slice(buf)],
+        cmsg_buffer: None,
+      })
+      .collect();
+    let messages =
+      match socket::recvmmsg(fd, &mut data, MsgFlags::MSG_DONTWAIT, None) {
+        Ok(messages) => messages,
+        Err(nix::Error::Sys(Errno::EAGAIN)) => return Ok(vec![]),
+        Err(err) => return Err(err),
+      };
+    let received = messages
+      .into_iter()
+      .map(|message| match message.address {
+        Some(SockAddr::Inet(addr)) => (message.bytes, addr.to_std()),
+        _ => unreachable!(),
+      })
+      .collect();
+    Ok(received)
+  }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+  use super::sys;
+  use std::net::UdpSocket;
+  use std::os::unix::io::AsRawFd;
+
+  #[test]
+  fn send_receive_segmented_runs() {
+    let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+    receiver.set_nonblocking(true).unwrap();
+    let addr = receiver.local_addr().unwrap();
+
+    // Two runs for segmentation offload: three 3 byte messages, then two 5
+    // byte ones.
+    let data: Vec<Vec<u8>> =
+      vec![vec![1; 3], vec![2; 3], vec![3; 3], vec![4; 5], vec![5; 5]];
+    let messages: Vec<(&[u8], _)> =
+      data.iter().map(|message| (&message[..], addr)).collect();
+    for gso in &[sys::gso_supported(sender.as_raw_fd()), false] {
+      let sent = sys::send(sender.as_raw_fd(), &messages, *gso).unwrap();
+      assert_eq!(sent, 5);
+
+      let mut buf = vec![0; 16 * 8];
+      let mut buffers: Vec<&mut [u8]> = buf.chunks_mut(16).collect();
+      let mut received = vec![];
+      while received.len() < 5 {
+        let offset = received.len();
+        for (size, from) in
+          sys::receive(receiver.as_raw_fd(), &mut buffers[offset..]).unwrap()
+        {
+          assert_eq!(from, sender.local_addr().unwrap());
+          received.push(size);
+        }
+      }
+      assert_eq!(received, vec![3, 3, 3, 5, 5]);
+      for (buffer, message) in buffers.iter().zip(&data) {
+        assert_eq!(&buffer[..message.len()], &message[..]);
+      }
+    }
+  }
+}
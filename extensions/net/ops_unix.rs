@@ -36,9 +36,9 @@ pub fn into_string(s: std::ffi::OsString) -> Result<String, AnyError> {
   })
 }
 
-struct UnixListenerResource {
-  listener: AsyncRefCell<UnixListener>,
-  cancel: CancelHandle,
+pub struct UnixListenerResource {
+  pub listener: AsyncRefCell<UnixListener>,
+  pub cancel: CancelHandle,
 }
 
 impl Resource for UnixListenerResource {
@@ -72,8 +72,11 @@ pub struct UnixAddr {
 }
 
 #[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct UnixListenArgs {
   pub path: String,
+  #[serde(default)]
+  pub reuse_port: bool,
 }
 
 pub(crate) async fn accept_unix(
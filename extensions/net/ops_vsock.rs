@@ -0,0 +1,288 @@
+// Copyright 2018-2021 the Deno authors. All rights reserved. MIT license.
+
+//! The `vsock` transport, for talking to hypervisors and to virtual machines
+//! or enclaves that have no network interface.
+
+use crate::io::VsockStreamResource;
+use crate::ops::AcceptArgs;
+use crate::ops::OpAddr;
+use crate::ops::OpConn;
+use deno_core::error::bad_resource;
+use deno_core::error::custom_error;
+use deno_core::error::AnyError;
+use deno_core::AsyncRefCell;
+use deno_core::CancelHandle;
+use deno_core::CancelTryFuture;
+use deno_core::OpState;
+use deno_core::RcRef;
+use deno_core::Resource;
+use nix::libc;
+use nix::sys::socket;
+use nix::sys::socket::AddressFamily;
+use nix::sys::socket::SockAddr;
+use nix::sys::socket::SockFlag;
+use nix::sys::socket::SockType;
+use nix::sys::socket::VsockAddr as SockVsockAddr;
+use serde::Deserialize;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::io;
+use std::os::unix::io::AsRawFd;
+use std::os::unix::io::RawFd;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::Context;
+use std::task::Poll;
+use tokio::io::unix::AsyncFd;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
+use tokio::io::ReadBuf;
+
+#[derive(Serialize)]
+pub struct VsockAddr {
+  pub cid: u32,
+  pub port: u32,
+}
+
+#[derive(Deserialize)]
+pub struct VsockListenArgs {
+  pub cid: u32,
+  pub port: u32,
+}
+
+fn to_io_error(err: nix::Error) -> io::Error {
+  match err {
+    nix::Error::Sys(errno) => io::Error::from_raw_os_error(errno as i32),
+    other => io::Error::new(io::ErrorKind::Other, other),
+  }
+}
+
+/// An owned, non-blocking `AF_VSOCK` socket.
+struct Socket(RawFd);
+
+impl Socket {
+  fn new() -> io::Result<Self> {
+    let fd = socket::socket(
+      AddressFamily::Vsock,
+      SockType::Stream,
+      SockFlag::SOCK_CLOEXEC | SockFlag::SOCK_NONBLOCK,
+      None,
+    )
+    .map_err(to_io_error)?;
+    Ok(Self(fd))
+  }
+
+  fn addr(&self, peer: bool) -> io::Result<VsockAddr> {
+    let addr = if peer {
+      socket::getpeername(self.0)
+    } else {
+      socket::getsockname(self.0)
+    };
+    match addr.map_err(to_io_error)? {
+      SockAddr::Vsock(addr) => Ok(VsockAddr {
+        cid: addr.cid(),
+        port: addr.port(),
+      }),
+      _ => unreachable!(),
+    }
+  }
+}
+
+impl AsRawFd for Socket {
+  fn as_raw_fd(&self) -> RawFd {
+    self.0
+  }
+}
+
+impl Drop for Socket {
+  fn drop(&mut self) {
+    let _ = nix::unistd::close(self.0);
+  }
+}
+
+pub struct VsockStream {
+  inner: AsyncFd<Socket>,
+}
+
+impl VsockStream {
+  pub async fn connect(cid: u32, port: u32) -> io::Result<Self> {
+    let socket = Socket::new()?;
+    let addr = SockAddr::Vsock(SockVsockAddr::new(cid, port));
+    let pending = match socket::connect(socket.0, &addr) {
+      Ok(()) => false,
+      Err(nix::Error::Sys(nix::errno::Errno::EINPROGRESS)) => true,
+      Err(err) => return Err(to_io_error(err)),
+    };
+    let inner = AsyncFd::new(socket)?;
+    if pending {
+      // The socket becomes writable once the connection attempt completes,
+      // successfully or not.
+      let _guard = inner.writable().await?;
+      let err =
+        socket::getsockopt(inner.as_raw_fd(), socket::sockopt::SocketError)
+          .map_err(to_io_error)?;
+      if err != 0 {
+        return Err(io::Error::from_raw_os_error(err));
+      }
+    }
+    Ok(Self { inner })
+  }
+
+  pub fn local_addr(&self) -> io::Result<VsockAddr> {
+    self.inner.get_ref().addr(false)
+  }
+
+  pub fn peer_addr(&self) -> io::Result<VsockAddr> {
+    self.inner.get_ref().addr(true)
+  }
+}
+
+impl AsyncRead for VsockStream {
+  fn poll_read(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    buf: &mut ReadBuf<'_>,
+  ) -> Poll<io::Result<()>> {
+    loop {
+      let mut guard = match self.inner.poll_read_ready(cx) {
+        Poll::Ready(guard) => guard?,
+        Poll::Pending => return Poll::Pending,
+      };
+      let unfilled = buf.initialize_unfilled();
+      let result = guard.try_io(|inner| {
+        nix::unistd::read(inner.as_raw_fd(), unfilled).map_err(to_io_error)
+      });
+      match result {
+        Ok(Ok(nread)) => {
+          buf.advance(nread);
+          return Poll::Ready(Ok(()));
+        }
+        Ok(Err(err)) => return Poll::Ready(Err(err)),
+        Err(_would_block) => continue,
+      }
+    }
+  }
+}
+
+impl AsyncWrite for VsockStream {
+  fn poll_write(
+    self: Pin<&mut Self>,
+    cx: &mut Context<'_>,
+    buf: &[u8],
+  ) -> Poll<io::Result<usize>> {
+    loop {
+      let mut guard = match self.inner.poll_write_ready(cx) {
+        Poll::Ready(guard) => guard?,
+        Poll::Pending => return Poll::Pending,
+      };
+      let result = guard.try_io(|inner| {
+        nix::unistd::write(inner.as_raw_fd(), buf).map_err(to_io_error)
+      });
+      match result {
+        Ok(result) => return Poll::Ready(result),
+        Err(_would_block) => continue,
+      }
+    }
+  }
+
+  fn poll_flush(
+    self: Pin<&mut Self>,
+    _cx: &mut Context<'_>,
+  ) -> Poll<io::Result<()>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn poll_shutdown(
+    self: Pin<&mut Self>,
+    _cx: &mut Context<'_>,
+  ) -> Poll<io::Result<()>> {
+    let result =
+      socket::shutdown(self.inner.as_raw_fd(), socket::Shutdown::Write)
+        .map_err(to_io_error);
+    Poll::Ready(result)
+  }
+}
+
+struct VsockListenerResource {
+  listener: AsyncRefCell<AsyncFd<Socket>>,
+  cancel: CancelHandle,
+}
+
+impl Resource for VsockListenerResource {
+  fn name(&self) -> Cow<str> {
+    "vsockListener".into()
+  }
+
+  fn close(self: Rc<Self>) {
+    self.cancel.cancel();
+  }
+}
+
+pub(crate) async fn accept_vsock(
+  state: Rc<RefCell<OpState>>,
+  args: AcceptArgs,
+  _: (),
+) -> Result<OpConn, AnyError> {
+  let rid = args.rid;
+
+  let resource = state
+    .borrow()
+    .resource_table
+    .get::<VsockListenerResource>(rid)
+    .ok_or_else(|| bad_resource("Listener has been closed"))?;
+  let listener = RcRef::map(&resource, |r| &r.listener)
+    .try_borrow_mut()
+    .ok_or_else(|| custom_error("Busy", "Listener already in use"))?;
+  let cancel = RcRef::map(resource, |r| &r.cancel);
+  let accept = async {
+    loop {
+      let mut guard = listener.readable().await?;
+      let result = guard.try_io(|inner| {
+        socket::accept4(
+          inner.as_raw_fd(),
+          SockFlag::SOCK_CLOEXEC | SockFlag::SOCK_NONBLOCK,
+        )
+        .map_err(to_io_error)
+      });
+      match result {
+        Ok(result) => return result.map(Socket),
+        Err(_would_block) => continue,
+      }
+    }
+  };
+  let socket = accept.try_or_cancel(cancel).await?;
+
+  let local_addr = socket.addr(false)?;
+  let remote_addr = socket.addr(true)?;
+  let stream = VsockStream {
+    inner: AsyncFd::new(socket)?,
+  };
+  let resource = VsockStreamResource::new(tokio::io::split(stream));
+  let mut state = state.borrow_mut();
+  let rid = state.resource_table.add(resource);
+  Ok(OpConn {
+    rid,
+    local_addr: Some(OpAddr::Vsock(local_addr)),
+    remote_addr: Some(OpAddr::Vsock(remote_addr)),
+  })
+}
+
+pub fn listen_vsock(
+  state: &mut OpState,
+  cid: u32,
+  port: u32,
+) -> Result<(u32, VsockAddr), AnyError> {
+  let socket = Socket::new()?;
+  let addr = SockAddr::Vsock(SockVsockAddr::new(cid, port));
+  socket::bind(socket.0, &addr).map_err(to_io_error)?;
+  socket::listen(socket.0, libc::SOMAXCONN as usize).map_err(to_io_error)?;
+  let local_addr = socket.addr(false)?;
+  let listener_resource = VsockListenerResource {
+    listener: AsyncRefCell::new(AsyncFd::new(socket)?),
+    cancel: Default::default(),
+  };
+  let rid = state.resource_table.add(listener_resource);
+
+  Ok((rid, local_addr))
+}
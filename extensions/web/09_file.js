@@ -94,7 +94,13 @@
   /** @param {(BlobReference | Blob)[]} parts */
   async function* toIterator(parts) {
     for (const part of parts) {
-      yield* part.stream();
+      if (part instanceof Blob) {
+        // Walk nested blobs directly instead of through their ReadableStream,
+        // so every leaf part is pulled on demand by the outermost stream.
+        yield* toIterator(part[_parts]);
+      } else {
+        yield* part.stream();
+      }
     }
   }
 
@@ -548,21 +554,46 @@
      * @returns {AsyncGenerator<Uint8Array>}
      */
     async *stream() {
-      yield core.opAsync("op_blob_read_part", this._id);
-
-      // let position = 0;
-      // const end = this.size;
-      // while (position !== end) {
-      //   const size = MathMin(end - position, 65536);
-      //   const chunk = this.slice(position, position + size);
-      //   position += chunk.size;
-      //   yield core.opAsync("op_blob_read_part", chunk._id);
-      // }
+      if (this.size <= STREAM_CHUNK_SIZE) {
+        yield core.opAsync("op_blob_read_part", this._id);
+        return;
+      }
+
+      // Read large parts in chunks, so that parts backed by something other
+      // than memory (like files) are never loaded in their entirety.
+      let position = 0;
+      const end = this.size;
+      while (position !== end) {
+        const size = MathMin(end - position, STREAM_CHUNK_SIZE);
+        const chunk = this.slice(position, position + size);
+        position += chunk.size;
+        yield core.opAsync("op_blob_read_part", chunk._id);
+      }
     }
   }
 
+  const STREAM_CHUNK_SIZE = 65536;
+
+  /**
+   * Construct a File whose contents are an existing blob part in Rust, for
+   * example one that lazily reads from disk.
+   *
+   * @param {string} id
+   * @param {number} size
+   * @param {string} name
+   * @param {FilePropertyBag} options
+   * @returns {File}
+   */
+  function fileFromPart(id, size, name, options) {
+    const file = new File([], name, options);
+    file[_parts] = [new BlobReference(id, size)];
+    file[_size] = size;
+    return file;
+  }
+
   window.__bootstrap.file = {
     getParts,
+    fileFromPart,
     Blob,
     File,
   };
@@ -4,11 +4,16 @@ use deno_core::parking_lot::Mutex;
 use deno_core::url::Url;
 use deno_core::ZeroCopyBuf;
 use serde::Deserialize;
+use std::borrow::Cow;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::io::SeekFrom;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncSeekExt;
 
 use deno_core::error::AnyError;
 use uuid::Uuid;
@@ -97,7 +102,7 @@ impl Blob {
 
     for part in &self.parts {
       let chunk = part.read().await?;
-      bytes.extend_from_slice(chunk);
+      bytes.extend_from_slice(&chunk);
     }
 
     assert_eq!(bytes.len(), size);
@@ -117,8 +122,19 @@ impl Blob {
 #[async_trait]
 pub trait BlobPart: Debug {
   // TODO(lucacsonato): this should be a stream!
-  async fn read(&self) -> Result<&[u8], AnyError>;
+  async fn read(&self) -> Result<Cow<'_, [u8]>, AnyError>;
   fn size(&self) -> usize;
+
+  /// Create a new part covering `len` bytes starting at `start`, without
+  /// reading the contents of this part. Parts that can't do this return
+  /// `None`, and are sliced with a `SlicedBlobPart` instead.
+  fn slice(
+    &self,
+    _start: usize,
+    _len: usize,
+  ) -> Option<Box<dyn BlobPart + Send + Sync>> {
+    None
+  }
 }
 
 #[derive(Debug)]
@@ -132,8 +148,8 @@ impl From<Vec<u8>> for InMemoryBlobPart {
 
 #[async_trait]
 impl BlobPart for InMemoryBlobPart {
-  async fn read(&self) -> Result<&[u8], AnyError> {
-    Ok(&self.0)
+  async fn read(&self) -> Result<Cow<'_, [u8]>, AnyError> {
+    Ok(Cow::Borrowed(&self.0))
   }
 
   fn size(&self) -> usize {
@@ -150,14 +166,63 @@ pub struct SlicedBlobPart {
 
 #[async_trait]
 impl BlobPart for SlicedBlobPart {
-  async fn read(&self) -> Result<&[u8], AnyError> {
-    let original = self.part.read().await?;
-    Ok(&original[self.start..self.start + self.len])
+  async fn read(&self) -> Result<Cow<'_, [u8]>, AnyError> {
+    let range = self.start..self.start + self.len;
+    Ok(match self.part.read().await? {
+      Cow::Borrowed(original) => Cow::Borrowed(&original[range]),
+      Cow::Owned(original) => Cow::Owned(original[range].to_vec()),
+    })
+  }
+
+  fn size(&self) -> usize {
+    self.len
+  }
+}
+
+/// A blob part backed by a byte range of a file on disk. The contents are
+/// only read when the part is, and slicing it doesn't touch the file.
+#[derive(Debug)]
+pub struct FileBlobPart {
+  path: PathBuf,
+  start: u64,
+  len: usize,
+}
+
+impl FileBlobPart {
+  pub fn new(path: PathBuf, len: usize) -> Self {
+    Self {
+      path,
+      start: 0,
+      len,
+    }
+  }
+}
+
+#[async_trait]
+impl BlobPart for FileBlobPart {
+  async fn read(&self) -> Result<Cow<'_, [u8]>, AnyError> {
+    let mut file = tokio::fs::File::open(&self.path).await?;
+    file.seek(SeekFrom::Start(self.start)).await?;
+    let mut buf = vec![0; self.len];
+    file.read_exact(&mut buf).await?;
+    Ok(Cow::Owned(buf))
   }
 
   fn size(&self) -> usize {
     self.len
   }
+
+  fn slice(
+    &self,
+    start: usize,
+    len: usize,
+  ) -> Option<Box<dyn BlobPart + Send + Sync>> {
+    Some(Box::new(FileBlobPart {
+      path: self.path.clone(),
+      start: self.start + start as u64,
+      len,
+    }))
+  }
 }
 
 pub fn op_blob_create_part(
@@ -197,8 +262,12 @@ pub fn op_blob_slice_part(
     ));
   }
 
-  let sliced_part = SlicedBlobPart { part, start, len };
-  let id = blob_store.insert_part(Box::new(sliced_part));
+  let maybe_sliced_part = part.slice(start, len);
+  let sliced_part = match maybe_sliced_part {
+    Some(sliced_part) => sliced_part,
+    None => Box::new(SlicedBlobPart { part, start, len }),
+  };
+  let id = blob_store.insert_part(sliced_part);
 
   Ok(id)
 }
@@ -215,7 +284,7 @@ pub async fn op_blob_read_part(
   }
   .ok_or_else(|| type_error("Blob part not found"))?;
   let buf = part.read().await?;
-  Ok(ZeroCopyBuf::from(buf.to_vec()))
+  Ok(ZeroCopyBuf::from(buf.into_owned()))
 }
 
 pub fn op_blob_remove_part(
@@ -37,6 +37,7 @@ use crate::blob::op_blob_slice_part;
 pub use crate::blob::Blob;
 pub use crate::blob::BlobPart;
 pub use crate::blob::BlobStore;
+pub use crate::blob::FileBlobPart;
 pub use crate::blob::InMemoryBlobPart;
 
 pub use crate::message_port::create_entangled_message_port;
@@ -3191,7 +3191,7 @@
         prefix,
         context: "Argument 3",
       });
-      destination = webidl.converters.GPUQuerySet(destination, {
+      destination = webidl.converters.GPUBuffer(destination, {
         prefix,
         context: "Argument 4",
       });
@@ -3215,11 +3215,11 @@
       });
       const destinationRid = assertResource(destination, {
         prefix,
-        context: "Argument 3",
+        context: "Argument 4",
       });
       assertDeviceMatch(device, destination, {
         prefix,
-        resourceContext: "Argument 3",
+        resourceContext: "Argument 4",
         selfContext: "this",
       });
       const { err } = core.opSync(
@@ -6,10 +6,12 @@
   const { read, readSync, write, writeSync } = window.__bootstrap.io;
   const { ftruncate, ftruncateSync, fstat, fstatSync } = window.__bootstrap.fs;
   const { pathFromURL } = window.__bootstrap.util;
+  const { fileFromPart } = window.__bootstrap.file;
   const {
     Error,
     ObjectValues,
     ArrayPrototypeFilter,
+    StringPrototypeSplit,
   } = window.__bootstrap.primordials;
 
   function seekSync(
@@ -56,6 +58,17 @@
     return new File(rid);
   }
 
+  async function openBlob(path, options = {}) {
+    path = pathFromURL(path);
+    const { id, size, mtime } = await core.opAsync("op_open_blob_async", path);
+    const components = StringPrototypeSplit(path, /[\\/]/);
+    const name = components[components.length - 1];
+    return fileFromPart(id, size, name, {
+      type: options.type,
+      lastModified: mtime ?? undefined,
+    });
+  }
+
   function createSync(path) {
     return openSync(path, {
       read: true,
@@ -231,6 +244,7 @@
     createSync,
     open,
     openSync,
+    openBlob,
     seek,
     seekSync,
   };
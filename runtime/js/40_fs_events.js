@@ -13,8 +13,21 @@
     #rid = 0;
 
     constructor(paths, options) {
-      const { recursive } = options;
-      this.#rid = core.opSync("op_fs_events_open", { recursive, paths });
+      const {
+        recursive,
+        debounce,
+        include,
+        exclude,
+        dedupe = false,
+      } = options;
+      this.#rid = core.opSync("op_fs_events_open", {
+        recursive,
+        paths,
+        debounce,
+        include,
+        exclude,
+        dedupe,
+      });
     }
 
     get rid() {
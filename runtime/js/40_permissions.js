@@ -48,10 +48,11 @@
 
   /**
    * @param {Deno.PermissionDescriptor} desc
+   * @param {boolean} propagate
    * @returns {Deno.PermissionState}
    */
-  function opRevoke(desc) {
-    return core.opSync("op_revoke_permission", desc);
+  function opRevoke(desc, propagate) {
+    return core.opSync("op_revoke_permission", desc, propagate);
   }
 
   /**
@@ -165,7 +166,7 @@
       return PromiseResolve(cache(desc, state));
     }
 
-    revoke(desc) {
+    revoke(desc, options = {}) {
       if (!isValidDescriptor(desc)) {
         return PromiseReject(
           new TypeError(
@@ -173,7 +174,7 @@
           ),
         );
       }
-      const state = opRevoke(desc);
+      const state = opRevoke(desc, options?.propagate ?? true);
       return PromiseResolve(cache(desc, state));
     }
 
@@ -4,7 +4,7 @@
 ((window) => {
   const core = window.Deno.core;
   const { File } = window.__bootstrap.files;
-  const { readAll } = window.__bootstrap.io;
+  const { read, readAll, write } = window.__bootstrap.io;
   const { assert, pathFromURL } = window.__bootstrap.util;
   const {
     ArrayPrototypeMap,
@@ -40,11 +40,47 @@
     }
   }
 
+  class Pty {
+    #rid = 0;
+
+    constructor(rid) {
+      this.#rid = rid;
+    }
+
+    get rid() {
+      return this.#rid;
+    }
+
+    read(p) {
+      return read(this.rid, p);
+    }
+
+    write(p) {
+      return write(this.rid, p);
+    }
+
+    resize({ rows, cols }) {
+      core.opSync("op_pty_resize", this.rid, { rows, cols });
+    }
+
+    setRaw(mode) {
+      core.opSync("op_pty_set_raw", this.rid, mode);
+    }
+
+    close() {
+      core.close(this.rid);
+    }
+  }
+
   class Process {
     constructor(res) {
       this.rid = res.rid;
       this.pid = res.pid;
 
+      if (res.ptyRid) {
+        this.pty = new Pty(res.ptyRid);
+      }
+
       if (res.stdinRid && res.stdinRid > 0) {
         this.stdin = new File(res.stdinRid);
       }
@@ -91,6 +127,10 @@
     kill(signo) {
       opKill(this.pid, signo);
     }
+
+    killTree(signo) {
+      core.opSync("op_kill_tree", this.rid, signo);
+    }
   }
 
   function isRid(arg) {
@@ -104,10 +144,18 @@
     stdout = "inherit",
     stderr = "inherit",
     stdin = "inherit",
+    pty = false,
+    processGroup = false,
   }) {
     if (cmd[0] != null) {
       cmd[0] = pathFromURL(cmd[0]);
     }
+    if (
+      pty &&
+      (stdin !== "inherit" || stdout !== "inherit" || stderr !== "inherit")
+    ) {
+      throw new TypeError("stdio options can't be combined with pty");
+    }
     const res = opRun({
       cmd: ArrayPrototypeMap(cmd, String),
       cwd,
@@ -118,6 +166,8 @@
       stdinRid: isRid(stdin) ? stdin : 0,
       stdoutRid: isRid(stdout) ? stdout : 0,
       stderrRid: isRid(stderr) ? stderr : 0,
+      pty: pty ? { rows: 24, cols: 80, ...(pty === true ? {} : pty) } : null,
+      processGroup,
     });
     return new Process(res);
   }
@@ -8,15 +8,24 @@
   const { Console, inspectArgs } = window.__bootstrap.console;
   const { metrics } = window.__bootstrap.metrics;
   const { assert } = window.__bootstrap.util;
+  const { TimedOut } = window.__bootstrap.errors.errors;
   const {
     ArrayPrototypeFilter,
+    ArrayPrototypeJoin,
     ArrayPrototypePush,
     DateNow,
     JSONStringify,
+    MathImul,
+    NumberIsFinite,
+    NumberParseInt,
+    ObjectEntries,
+    ObjectPrototypeHasOwnProperty,
     Promise,
+    PromiseRace,
     TypeError,
     StringPrototypeStartsWith,
     StringPrototypeEndsWith,
+    StringPrototypeCharCodeAt,
     StringPrototypeIncludes,
     StringPrototypeSlice,
     RegExp,
@@ -128,6 +137,104 @@ finishing test case.`;
     };
   }
 
+  // Timeout applied to tests that don't specify one, set from the
+  // `--timeout` flag of `deno test`.
+  let defaultTimeout = null;
+
+  // Largest delay `setTimeout` accepts; anything above is clamped to 1ms.
+  const TIMEOUT_MAX = 2 ** 31 - 1;
+
+  // Name of a timed out test that left async ops pending even after its
+  // resources were closed. Those ops may complete while a later test runs and
+  // trip its sanitizers, so the remaining tests in the module aren't run.
+  let poisonedBy = null;
+
+  // Describe what a timed out test was still waiting on: async ops that were
+  // started during the test and have not completed yet (per-op metrics are
+  // only available with `--unstable`) and resources opened during the test.
+  // Those resources are closed so that the ops waiting on them settle instead
+  // of leaking into the following tests.
+  function describeTimeout(ms, preMetrics, preResources) {
+    const postMetrics = metrics();
+    const postResources = core.resources();
+    let message = `Test timed out after ${ms}ms.`;
+
+    if (preMetrics.ops && postMetrics.ops) {
+      const pending = [];
+      for (const [name, op] of ObjectEntries(postMetrics.ops)) {
+        const before = preMetrics.ops[name];
+        const count = (op.opsDispatchedAsync - op.opsCompletedAsync) -
+          (before ? before.opsDispatchedAsync - before.opsCompletedAsync : 0);
+        if (count > 0) {
+          ArrayPrototypePush(pending, `  - ${name} (${count})`);
+        }
+      }
+      if (pending.length > 0) {
+        message += `\nPending async ops started by the test:\n${
+          ArrayPrototypeJoin(pending, "\n")
+        }`;
+      }
+    }
+
+    const opened = [];
+    for (const [rid, name] of ObjectEntries(postResources)) {
+      if (!ObjectPrototypeHasOwnProperty(preResources, rid)) {
+        ArrayPrototypePush(opened, `  - ${name} (rid ${rid})`);
+        core.close(NumberParseInt(rid, 10));
+      }
+    }
+    if (opened.length > 0) {
+      message += `\nResources opened by the test (now closed):\n${
+        ArrayPrototypeJoin(opened, "\n")
+      }`;
+    }
+
+    return message;
+  }
+
+  // Wrap test function so that it fails once it has been running for longer
+  // than its timeout. The timer is started inside of the sanitizers, so it
+  // doesn't show up as a leaking op when the test finishes in time.
+  function withTimeout(fn, name, timeout) {
+    return async function timeoutGuard() {
+      const ms = timeout ?? defaultTimeout;
+      if (ms == null) {
+        await fn();
+        return;
+      }
+
+      const preMetrics = metrics();
+      const preResources = core.resources();
+      let timeoutId;
+      let timedOut = false;
+      const deadline = new Promise((_resolve, reject) => {
+        timeoutId = setTimeout(() => {
+          timedOut = true;
+          core.opSync("op_test_timed_out");
+          reject(new TimedOut(describeTimeout(ms, preMetrics, preResources)));
+        }, ms);
+      });
+
+      try {
+        await PromiseRace([fn(), deadline]);
+      } finally {
+        clearTimeout(timeoutId);
+        if (timedOut) {
+          // Give the ops waiting on the closed resources a turn to settle.
+          await new Promise((resolve) => setTimeout(resolve, 0));
+          const postMetrics = metrics();
+          const pendingBefore = preMetrics.opsDispatchedAsync -
+            preMetrics.opsCompletedAsync;
+          const pendingAfter = postMetrics.opsDispatchedAsync -
+            postMetrics.opsCompletedAsync;
+          if (pendingAfter > pendingBefore) {
+            poisonedBy = name;
+          }
+        }
+      }
+    };
+  }
+
   const tests = [];
 
   // Main test function provided by Deno, as you can see it merely
@@ -144,6 +251,7 @@ finishing test case.`;
       sanitizeResources: true,
       sanitizeExit: true,
       permissions: null,
+      timeout: null,
     };
 
     if (typeof t === "string") {
@@ -164,6 +272,18 @@ finishing test case.`;
       testDef = { ...defaults, ...t };
     }
 
+    if (
+      testDef.timeout != null &&
+      (typeof testDef.timeout !== "number" || !(testDef.timeout > 0) ||
+        !NumberIsFinite(testDef.timeout) || testDef.timeout > TIMEOUT_MAX)
+    ) {
+      throw new TypeError(
+        `The test timeout must be a positive number no greater than ${TIMEOUT_MAX}`,
+      );
+    }
+
+    testDef.fn = withTimeout(testDef.fn, testDef.name, testDef.timeout);
+
     if (testDef.sanitizeOps) {
       testDef.fn = assertOps(testDef.fn);
     }
@@ -206,11 +326,44 @@ finishing test case.`;
     };
   }
 
+  // FNV-1a followed by the murmur3 finalizer, so that similar names still
+  // spread evenly across shards.
+  function hashTestName(name) {
+    let hash = 0x811c9dc5;
+    for (let i = 0; i < name.length; i++) {
+      hash ^= StringPrototypeCharCodeAt(name, i);
+      hash = MathImul(hash, 0x01000193);
+    }
+    hash ^= hash >>> 16;
+    hash = MathImul(hash, 0x85ebca6b);
+    hash ^= hash >>> 13;
+    hash = MathImul(hash, 0xc2b2ae35);
+    hash ^= hash >>> 16;
+    return hash >>> 0;
+  }
+
+  function createTestShardFilter(shard) {
+    return (def) => {
+      if (shard) {
+        return hashTestName(def.name) % shard.count === shard.index - 1;
+      }
+
+      return true;
+    };
+  }
+
   async function runTest({ ignore, fn }) {
     if (ignore) {
       return "ignored";
     }
 
+    if (poisonedBy !== null) {
+      return {
+        "failed":
+          `Not run: test "${poisonedBy}" timed out and left async ops pending.`,
+      };
+    }
+
     try {
       await fn();
       return "ok";
@@ -231,7 +384,10 @@ finishing test case.`;
     disableLog = false,
     filter = null,
     shuffle = null,
+    shard = null,
+    timeout = null,
   } = {}) {
+    defaultTimeout = timeout;
     const origin = getTestOrigin();
     const originalConsole = globalThis.console;
     if (disableLog) {
@@ -240,8 +396,11 @@ finishing test case.`;
 
     const only = ArrayPrototypeFilter(tests, (test) => test.only);
     const filtered = ArrayPrototypeFilter(
-      (only.length > 0 ? only : tests),
-      createTestFilter(filter),
+      ArrayPrototypeFilter(
+        (only.length > 0 ? only : tests),
+        createTestFilter(filter),
+      ),
+      createTestShardFilter(shard),
     );
 
     dispatchTestEvent({
@@ -0,0 +1,44 @@
+// Copyright 2018-2021 the Deno authors. All rights reserved. MIT license.
+"use strict";
+
+((window) => {
+  const core = window.Deno.core;
+  const { File } = window.__bootstrap.files;
+  const { Conn, Listener } = window.__bootstrap.net;
+  const { ArrayPrototypeMap } = window.__bootstrap.primordials;
+
+  function sendResources(conn, resources, data = new Uint8Array(1)) {
+    return core.opAsync(
+      "op_send_resources",
+      { rid: conn.rid, rids: ArrayPrototypeMap(resources, (r) => r.rid) },
+      data,
+    );
+  }
+
+  async function receiveResources(conn, buffer = new Uint8Array(1)) {
+    const { nread, resources, truncated } = await core.opAsync(
+      "op_receive_resources",
+      conn.rid,
+      buffer,
+    );
+    return {
+      nread: nread === 0 ? null : nread,
+      resources: ArrayPrototypeMap(resources, (res) => {
+        switch (res.kind) {
+          case "file":
+            return new File(res.rid);
+          case "conn":
+            return new Conn(res.rid, res.remoteAddr, res.localAddr);
+          case "listener":
+            return new Listener(res.rid, res.localAddr);
+        }
+      }),
+      truncated,
+    };
+  }
+
+  window.__bootstrap.fdPassing = {
+    sendResources,
+    receiveResources,
+  };
+})(this);
@@ -127,6 +127,8 @@
     connect: __bootstrap.netUnstable.connect,
     listenDatagram: __bootstrap.netUnstable.listenDatagram,
     serveHttp: __bootstrap.http.serveHttp,
+    sendResources: __bootstrap.fdPassing.sendResources,
+    receiveResources: __bootstrap.fdPassing.receiveResources,
     startTls: __bootstrap.tls.startTls,
     umask: __bootstrap.fs.umask,
     upgradeWebSocket: __bootstrap.http.upgradeWebSocket,
@@ -134,6 +136,7 @@
     futimeSync: __bootstrap.fs.futimeSync,
     utime: __bootstrap.fs.utime,
     utimeSync: __bootstrap.fs.utimeSync,
+    openBlob: __bootstrap.files.openBlob,
     HttpClient: __bootstrap.fetch.HttpClient,
     createHttpClient: __bootstrap.fetch.createHttpClient,
     http: __bootstrap.http,
@@ -0,0 +1,469 @@
+// Copyright 2018-2021 the Deno authors. All rights reserved. MIT license.
+
+//! Passing resources to other processes over Unix sockets, by sending their
+//! file descriptors as `SCM_RIGHTS` ancillary data.
+
+use deno_core::op_async;
+use deno_core::Extension;
+use deno_core::ResourceId;
+use deno_net::ops::OpConn;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[cfg(not(unix))]
+use deno_core::error::not_supported;
+#[cfg(not(unix))]
+use deno_core::error::AnyError;
+#[cfg(not(unix))]
+use deno_core::OpState;
+#[cfg(not(unix))]
+use deno_core::ZeroCopyBuf;
+#[cfg(not(unix))]
+use std::cell::RefCell;
+#[cfg(not(unix))]
+use std::rc::Rc;
+
+pub fn init() -> Extension {
+  Extension::builder()
+    .ops(vec![
+      ("op_send_resources", op_async(op_send_resources)),
+      ("op_receive_resources", op_async(op_receive_resources)),
+    ])
+    .build()
+}
+
+#[derive(Deserialize)]
+pub struct SendResourcesArgs {
+  rid: ResourceId,
+  rids: Vec<ResourceId>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ReceivedResource {
+  File { rid: ResourceId },
+  Conn(OpConn),
+  Listener(OpConn),
+}
+
+impl ReceivedResource {
+  #[cfg(unix)]
+  fn rid(&self) -> ResourceId {
+    match self {
+      ReceivedResource::File { rid } => *rid,
+      ReceivedResource::Conn(conn) | ReceivedResource::Listener(conn) => {
+        conn.rid
+      }
+    }
+  }
+}
+
+#[derive(Serialize)]
+pub struct ReceiveResourcesResult {
+  nread: usize,
+  resources: Vec<ReceivedResource>,
+  /// More resources were sent than fit in the control message buffer. The
+  /// ones that didn't fit were closed by the kernel.
+  truncated: bool,
+}
+
+#[cfg(unix)]
+mod unix {
+  use super::ReceiveResourcesResult;
+  use super::ReceivedResource;
+  use super::SendResourcesArgs;
+  use crate::ops::check_unstable2;
+  use crate::ops::io::StdFileResource;
+  use deno_core::error::bad_resource_id;
+  use deno_core::error::custom_error;
+  use deno_core::error::null_opbuf;
+  use deno_core::error::resource_unavailable;
+  use deno_core::error::type_error;
+  use deno_core::error::AnyError;
+  use deno_core::AsyncRefCell;
+  use deno_core::CancelTryFuture;
+  use deno_core::OpState;
+  use deno_core::RcRef;
+  use deno_core::ResourceId;
+  use deno_core::ZeroCopyBuf;
+  use deno_net::io::TcpStreamResource;
+  use deno_net::io::UnixStreamResource;
+  use deno_net::ops::IpAddr;
+  use deno_net::ops::OpAddr;
+  use deno_net::ops::OpConn;
+  use deno_net::ops::TcpListenerResource;
+  use deno_net::ops_unix::pathstring;
+  use deno_net::ops_unix::UnixAddr;
+  use deno_net::ops_unix::UnixListenerResource;
+  use nix::cmsg_space;
+  use nix::fcntl::fcntl;
+  use nix::fcntl::FcntlArg;
+  use nix::sys::socket;
+  use nix::sys::socket::sockopt;
+  use nix::sys::socket::ControlMessage;
+  use nix::sys::socket::ControlMessageOwned;
+  use nix::sys::socket::MsgFlags;
+  use nix::sys::socket::SockAddr;
+  use nix::sys::socket::SockType;
+  use nix::sys::stat::fstat;
+  use nix::sys::stat::SFlag;
+  use nix::sys::uio::IoVec;
+  use std::cell::RefCell;
+  use std::io;
+  use std::os::unix::io::AsRawFd;
+  use std::os::unix::io::FromRawFd;
+  use std::os::unix::io::RawFd;
+  use std::rc::Rc;
+  use tokio::io::unix::AsyncFd;
+
+  /// The most resources that can be received in a single message. Any more
+  /// that were sent are closed, and the result is marked as truncated.
+  const MAX_FDS: usize = 16;
+
+  #[cfg(target_os = "linux")]
+  const RECV_FLAGS: MsgFlags = MsgFlags::MSG_CMSG_CLOEXEC;
+  #[cfg(not(target_os = "linux"))]
+  const RECV_FLAGS: MsgFlags = MsgFlags::empty();
+
+  fn to_io_error(err: nix::Error) -> io::Error {
+    match err {
+      nix::Error::Sys(errno) => io::Error::from_raw_os_error(errno as i32),
+      other => io::Error::new(io::ErrorKind::Other, other),
+    }
+  }
+
+  /// A file descriptor that is closed when dropped.
+  struct OwnedFd(RawFd);
+
+  impl OwnedFd {
+    fn dup(fd: RawFd) -> Result<Self, AnyError> {
+      let fd = fcntl(fd, FcntlArg::F_DUPFD_CLOEXEC(0))?;
+      Ok(Self(fd))
+    }
+
+    fn into_raw_fd(self) -> RawFd {
+      let fd = self.0;
+      std::mem::forget(self);
+      fd
+    }
+  }
+
+  impl AsRawFd for OwnedFd {
+    fn as_raw_fd(&self) -> RawFd {
+      self.0
+    }
+  }
+
+  impl Drop for OwnedFd {
+    fn drop(&mut self) {
+      let _ = nix::unistd::close(self.0);
+    }
+  }
+
+  /// Duplicates the file descriptor backing the resource `rid`.
+  async fn resource_fd(
+    state: &Rc<RefCell<OpState>>,
+    rid: ResourceId,
+  ) -> Result<OwnedFd, AnyError> {
+    let resource = state
+      .borrow()
+      .resource_table
+      .get_any(rid)
+      .ok_or_else(bad_resource_id)?;
+    if let Some(s) = resource.downcast_rc::<StdFileResource>() {
+      if s.fs_file.is_none() {
+        return Err(resource_unavailable());
+      }
+      let fs_file = RcRef::map(s, |r| r.fs_file.as_ref().unwrap())
+        .borrow()
+        .await;
+      let file = fs_file.0.as_ref().ok_or_else(resource_unavailable)?;
+      OwnedFd::dup(file.as_raw_fd())
+    } else if let Some(s) = resource.downcast_rc::<TcpStreamResource>() {
+      let wr = s.wr_borrow_mut().await;
+      let stream: &tokio::net::TcpStream = (*wr).as_ref();
+      OwnedFd::dup(stream.as_raw_fd())
+    } else if let Some(s) = resource.downcast_rc::<UnixStreamResource>() {
+      let wr = s.wr_borrow_mut().await;
+      let stream: &tokio::net::UnixStream = (*wr).as_ref();
+      OwnedFd::dup(stream.as_raw_fd())
+    } else if let Some(s) = resource.downcast_rc::<TcpListenerResource>() {
+      let listener =
+        RcRef::map(s, |r| &r.listener).try_borrow().ok_or_else(|| {
+          custom_error("Busy", "Another accept task is ongoing")
+        })?;
+      OwnedFd::dup(listener.as_raw_fd())
+    } else if let Some(s) = resource.downcast_rc::<UnixListenerResource>() {
+      let listener =
+        RcRef::map(s, |r| &r.listener).try_borrow().ok_or_else(|| {
+          custom_error("Busy", "Another accept task is ongoing")
+        })?;
+      OwnedFd::dup(listener.as_raw_fd())
+    } else {
+      Err(type_error("Resource can't be sent"))
+    }
+  }
+
+  /// Adds the resource for a file descriptor that was received, picking its
+  /// type from what the descriptor refers to.
+  fn add_resource(
+    state: &mut OpState,
+    fd: OwnedFd,
+  ) -> Result<ReceivedResource, AnyError> {
+    let raw_fd = fd.as_raw_fd();
+    let stat = fstat(raw_fd)?;
+    let is_socket = (SFlag::from_bits_truncate(stat.st_mode) & SFlag::S_IFMT)
+      == SFlag::S_IFSOCK;
+    if is_socket
+      && socket::getsockopt(raw_fd, sockopt::SockType)? == SockType::Stream
+    {
+      let listening = socket::getsockopt(raw_fd, sockopt::AcceptConn)?;
+      match socket::getsockname(raw_fd)? {
+        SockAddr::Inet(_) if listening => {
+          let listener =
+            unsafe { std::net::TcpListener::from_raw_fd(fd.into_raw_fd()) };
+          listener.set_nonblocking(true)?;
+          let listener = tokio::net::TcpListener::from_std(listener)?;
+          let local_addr = listener.local_addr()?;
+          let rid = state.resource_table.add(TcpListenerResource {
+            listener: AsyncRefCell::new(listener),
+            cancel: Default::default(),
+          });
+          return Ok(ReceivedResource::Listener(OpConn {
+            rid,
+            local_addr: Some(OpAddr::Tcp(IpAddr {
+              hostname: local_addr.ip().to_string(),
+              port: local_addr.port(),
+            })),
+            remote_addr: None,
+          }));
+        }
+        SockAddr::Inet(_) => {
+          let stream =
+            unsafe { std::net::TcpStream::from_raw_fd(fd.into_raw_fd()) };
+          stream.set_nonblocking(true)?;
+          let stream = tokio::net::TcpStream::from_std(stream)?;
+          let local_addr = stream.local_addr()?;
+          let remote_addr = stream.peer_addr()?;
+          let rid = state
+            .resource_table
+            .add(TcpStreamResource::new(stream.into_split()));
+          return Ok(ReceivedResource::Conn(OpConn {
+            rid,
+            local_addr: Some(OpAddr::Tcp(IpAddr {
+              hostname: local_addr.ip().to_string(),
+              port: local_addr.port(),
+            })),
+            remote_addr: Some(OpAddr::Tcp(IpAddr {
+              hostname: remote_addr.ip().to_string(),
+              port: remote_addr.port(),
+            })),
+          }));
+        }
+        SockAddr::Unix(_) if listening => {
+          let listener = unsafe {
+            std::os::unix::net::UnixListener::from_raw_fd(fd.into_raw_fd())
+          };
+          listener.set_nonblocking(true)?;
+          let listener = tokio::net::UnixListener::from_std(listener)?;
+          let local_addr = listener.local_addr()?;
+          let rid = state.resource_table.add(UnixListenerResource {
+            listener: AsyncRefCell::new(listener),
+            cancel: Default::default(),
+          });
+          return Ok(ReceivedResource::Listener(OpConn {
+            rid,
+            local_addr: Some(OpAddr::Unix(UnixAddr {
+              path: local_addr.as_pathname().and_then(pathstring),
+            })),
+            remote_addr: None,
+          }));
+        }
+        SockAddr::Unix(_) => {
+          let stream = unsafe {
+            std::os::unix::net::UnixStream::from_raw_fd(fd.into_raw_fd())
+          };
+          stream.set_nonblocking(true)?;
+          let stream = tokio::net::UnixStream::from_std(stream)?;
+          let local_addr = stream.local_addr()?;
+          let remote_addr = stream.peer_addr()?;
+          let rid = state
+            .resource_table
+            .add(UnixStreamResource::new(stream.into_split()));
+          return Ok(ReceivedResource::Conn(OpConn {
+            rid,
+            local_addr: Some(OpAddr::Unix(UnixAddr {
+              path: local_addr.as_pathname().and_then(pathstring),
+            })),
+            remote_addr: Some(OpAddr::Unix(UnixAddr {
+              path: remote_addr.as_pathname().and_then(pathstring),
+            })),
+          }));
+        }
+        _ => {}
+      }
+    }
+
+    // Anything else, like files, pipes and other kinds of sockets, can at
+    // least be read from and written to.
+    let file = unsafe { std::fs::File::from_raw_fd(fd.into_raw_fd()) };
+    let rid = state
+      .resource_table
+      .add(StdFileResource::fs_file(tokio::fs::File::from_std(file)));
+    Ok(ReceivedResource::File { rid })
+  }
+
+  pub async fn op_send_resources(
+    state: Rc<RefCell<OpState>>,
+    args: SendResourcesArgs,
+    buf: Option<ZeroCopyBuf>,
+  ) -> Result<usize, AnyError> {
+    check_unstable2(&state, "Deno.sendResources");
+    let buf = buf.ok_or_else(null_opbuf)?;
+    // The resources are attached to the data, so there has to be some.
+    if buf.is_empty() {
+      return Err(type_error("Resources must be sent with at least one byte"));
+    }
+
+    let resource = state
+      .borrow()
+      .resource_table
+      .get::<UnixStreamResource>(args.rid)
+      .ok_or_else(bad_resource_id)?;
+    let mut fds = Vec::with_capacity(args.rids.len());
+    for rid in args.rids {
+      fds.push(resource_fd(&state, rid).await?);
+    }
+    let raw_fds: Vec<RawFd> = fds.iter().map(AsRawFd::as_raw_fd).collect();
+    let cmsgs = if raw_fds.is_empty() {
+      vec![]
+    } else {
+      vec![ControlMessage::ScmRights(&raw_fds)]
+    };
+
+    // Hold on to the write half so that this doesn't interleave with writes.
+    let wr = resource.wr_borrow_mut().await;
+    let stream: &tokio::net::UnixStream = (*wr).as_ref();
+    let fd = AsyncFd::new(OwnedFd::dup(stream.as_raw_fd())?)?;
+    loop {
+      let mut guard = fd.writable().await?;
+      let result = guard.try_io(|inner| {
+        let iov = [IoVec::from_slice(&buf)];
+        socket::sendmsg(
+          inner.as_raw_fd(),
+          &iov,
+          &cmsgs,
+          MsgFlags::empty(),
+          None,
+        )
+        .map_err(to_io_error)
+      });
+      match result {
+        Ok(result) => return Ok(result?),
+        Err(_would_block) => continue,
+      }
+    }
+  }
+
+  pub async fn op_receive_resources(
+    state: Rc<RefCell<OpState>>,
+    rid: ResourceId,
+    buf: Option<ZeroCopyBuf>,
+  ) -> Result<ReceiveResourcesResult, AnyError> {
+    check_unstable2(&state, "Deno.receiveResources");
+    let mut buf = buf.ok_or_else(null_opbuf)?;
+
+    let resource = state
+      .borrow()
+      .resource_table
+      .get::<UnixStreamResource>(rid)
+      .ok_or_else(bad_resource_id)?;
+    // Hold on to the read half so that this doesn't interleave with reads.
+    let rd = resource.rd_borrow_mut().await;
+    let stream: &tokio::net::UnixStream = (*rd).as_ref();
+    let fd = AsyncFd::new(OwnedFd::dup(stream.as_raw_fd())?)?;
+    let receive = async {
+      loop {
+        let mut guard = fd.readable().await?;
+        let result = guard.try_io(|inner| {
+          let iov = [IoVec::from_mut_slice(&mut buf)];
+          let mut cmsg_buffer = cmsg_space!([RawFd; MAX_FDS]);
+          let msg = socket::recvmsg(
+            inner.as_raw_fd(),
+            &iov,
+            Some(&mut cmsg_buffer),
+            RECV_FLAGS,
+          )
+          .map_err(to_io_error)?;
+          let mut fds = vec![];
+          for cmsg in msg.cmsgs() {
+            if let ControlMessageOwned::ScmRights(received) = cmsg {
+              fds.extend(received.into_iter().map(OwnedFd));
+            }
+          }
+          let truncated = msg.flags.contains(MsgFlags::MSG_CTRUNC);
+          Ok((msg.bytes, fds, truncated))
+        });
+        match result {
+          Ok(result) => return result,
+          Err(_would_block) => continue,
+        }
+      }
+    };
+    let (nread, fds, truncated) =
+      receive.try_or_cancel(resource.cancel_handle()).await?;
+    drop(rd);
+
+    #[cfg(not(target_os = "linux"))]
+    {
+      use nix::fcntl::FdFlag;
+      for fd in &fds {
+        fcntl(fd.as_raw_fd(), FcntlArg::F_SETFD(FdFlag::FD_CLOEXEC))?;
+      }
+    }
+
+    let mut state = state.borrow_mut();
+    let mut resources = Vec::with_capacity(fds.len());
+    for fd in fds {
+      match add_resource(&mut state, fd) {
+        Ok(resource) => resources.push(resource),
+        Err(err) => {
+          // The rids added so far never reach JS, so close them here. The
+          // remaining fds are closed when dropped.
+          for resource in resources {
+            state.resource_table.close(resource.rid());
+          }
+          return Err(err);
+        }
+      }
+    }
+    Ok(ReceiveResourcesResult {
+      nread,
+      resources,
+      truncated,
+    })
+  }
+}
+
+#[cfg(unix)]
+use unix::op_receive_resources;
+#[cfg(unix)]
+use unix::op_send_resources;
+
+#[cfg(not(unix))]
+async fn op_send_resources(
+  _state: Rc<RefCell<OpState>>,
+  _args: SendResourcesArgs,
+  _buf: Option<ZeroCopyBuf>,
+) -> Result<usize, AnyError> {
+  Err(not_supported())
+}
+
+#[cfg(not(unix))]
+async fn op_receive_resources(
+  _state: Rc<RefCell<OpState>>,
+  _rid: ResourceId,
+  _buf: Option<ZeroCopyBuf>,
+) -> Result<ReceiveResourcesResult, AnyError> {
+  Err(not_supported())
+}
@@ -16,6 +16,8 @@ use deno_core::RcRef;
 use deno_core::ResourceId;
 use deno_crypto::rand::thread_rng;
 use deno_crypto::rand::Rng;
+use deno_web::BlobStore;
+use deno_web::FileBlobPart;
 use log::debug;
 use serde::Deserialize;
 use serde::Serialize;
@@ -87,6 +89,7 @@ pub fn init() -> Extension {
       ("op_futime_async", op_async(op_futime_async)),
       ("op_utime_sync", op_sync(op_utime_sync)),
       ("op_utime_async", op_async(op_utime_async)),
+      ("op_open_blob_async", op_async(op_open_blob_async)),
     ])
     .build()
 }
@@ -1657,6 +1660,54 @@ async fn op_utime_async(
   .unwrap()
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenBlobResult {
+  id: String,
+  size: usize,
+  mtime: Option<u64>,
+}
+
+async fn op_open_blob_async(
+  state: Rc<RefCell<OpState>>,
+  path: String,
+  _: (),
+) -> Result<OpenBlobResult, AnyError> {
+  super::check_unstable(&state.borrow(), "Deno.openBlob");
+
+  let path = PathBuf::from(&path);
+
+  state
+    .borrow_mut()
+    .borrow_mut::<Permissions>()
+    .read
+    .check(&path)?;
+
+  let (path, metadata) = tokio::task::spawn_blocking(move || {
+    debug!("op_open_blob_async {}", path.display());
+    let path = canonicalize_path(&path)?;
+    let metadata = std::fs::metadata(&path)?;
+    Ok::<_, AnyError>((path, metadata))
+  })
+  .await
+  .unwrap()?;
+
+  if !metadata.is_file() {
+    return Err(type_error("Path is not a file"));
+  }
+
+  let size = metadata.len() as usize;
+  let part = FileBlobPart::new(path, size);
+  let state = state.borrow();
+  let id = state.borrow::<BlobStore>().insert_part(Box::new(part));
+
+  Ok(OpenBlobResult {
+    id: id.to_string(),
+    size,
+    mtime: to_msec(metadata.modified()),
+  })
+}
+
 fn op_cwd(state: &mut OpState, _args: (), _: ()) -> Result<String, AnyError> {
   let path = current_dir()?;
   state
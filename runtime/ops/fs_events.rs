@@ -1,7 +1,9 @@
 // Copyright 2018-2021 the Deno authors. All rights reserved. MIT license.
 
+use crate::fs_util::resolve_from_cwd;
 use crate::permissions::Permissions;
 use deno_core::error::bad_resource_id;
+use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::parking_lot::Mutex;
 use deno_core::AsyncRefCell;
@@ -15,6 +17,9 @@ use deno_core::ResourceId;
 use deno_core::op_async;
 use deno_core::op_sync;
 use deno_core::Extension;
+use globset::GlobBuilder;
+use globset::GlobSet;
+use globset::GlobSetBuilder;
 use notify::event::Event as NotifyEvent;
 use notify::Error as NotifyError;
 use notify::EventKind;
@@ -25,10 +30,23 @@ use serde::Deserialize;
 use serde::Serialize;
 use std::borrow::Cow;
 use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::convert::From;
+use std::hash::Hasher;
+use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
 use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+/// A burst of events is flushed after at most this many debounce periods, so
+/// that a steady stream of changes can't hold events back indefinitely.
+const MAX_DEBOUNCE_PERIODS: u32 = 10;
 
 pub fn init() -> Extension {
   Extension::builder()
@@ -42,10 +60,78 @@ pub fn init() -> Extension {
 struct FsEventsResource {
   #[allow(unused)]
   watcher: RecommendedWatcher,
-  receiver: AsyncRefCell<mpsc::Receiver<Result<FsEvent, AnyError>>>,
+  queue: AsyncRefCell<EventQueue>,
+  debounce: Option<Duration>,
   cancel: CancelHandle,
 }
 
+struct EventQueue {
+  receiver: mpsc::Receiver<Result<FsEvent, AnyError>>,
+  /// Coalesced events that have not been returned yet.
+  pending: VecDeque<FsEvent>,
+  /// Fingerprints of the files seen so far, when deduplicating.
+  fingerprints: Option<Arc<Mutex<HashMap<PathBuf, Fingerprint>>>>,
+}
+
+impl EventQueue {
+  async fn next(
+    &mut self,
+    debounce: Option<Duration>,
+  ) -> Result<Option<FsEvent>, AnyError> {
+    while self.pending.is_empty() {
+      let event = match self.receiver.recv().await {
+        Some(result) => result?,
+        None => return Ok(None),
+      };
+      self.pending.push_back(event);
+
+      // Keep collecting until no event arrived for a whole debounce period.
+      if let Some(debounce) = debounce {
+        let deadline = Instant::now() + debounce * MAX_DEBOUNCE_PERIODS;
+        loop {
+          let wake = std::cmp::min(Instant::now() + debounce, deadline);
+          match tokio::time::timeout_at(wake, self.receiver.recv()).await {
+            Ok(Some(Ok(event))) => coalesce(&mut self.pending, event),
+            Ok(Some(Err(err))) => return Err(err),
+            Ok(None) | Err(_) => break,
+          }
+        }
+      }
+
+      if let Some(fingerprints) = self.fingerprints.clone() {
+        // Checking for changes reads the files, so keep it off this thread.
+        let events: Vec<FsEvent> = self.pending.drain(..).collect();
+        let events = tokio::task::spawn_blocking(move || {
+          let mut fingerprints = fingerprints.lock();
+          events
+            .into_iter()
+            .filter_map(|event| dedupe(&mut fingerprints, event))
+            .collect::<Vec<_>>()
+        })
+        .await?;
+        self.pending.extend(events);
+      }
+    }
+    Ok(self.pending.pop_front())
+  }
+}
+
+/// Merges `event` into the last pending event if it is of the same kind.
+/// Only adjacent events are merged, so that e.g. a file that is created,
+/// removed and created again is still reported as existing.
+fn coalesce(pending: &mut VecDeque<FsEvent>, event: FsEvent) {
+  match pending.back_mut() {
+    Some(last) if last.kind == event.kind => {
+      for path in event.paths {
+        if !last.paths.contains(&path) {
+          last.paths.push(path);
+        }
+      }
+    }
+    _ => pending.push_back(event),
+  }
+}
+
 impl Resource for FsEventsResource {
   fn name(&self) -> Cow<str> {
     "fsEvents".into()
@@ -88,10 +174,187 @@ impl From<NotifyEvent> for FsEvent {
   }
 }
 
+/// Filters applied on the watcher thread, before events are queued.
+struct EventFilter {
+  /// The watched paths. Globs are matched against paths relative to these,
+  /// so that the directories they live in don't affect the result.
+  roots: Vec<PathBuf>,
+  include: Patterns,
+  exclude: Patterns,
+}
+
+impl EventFilter {
+  fn apply(&self, mut event: FsEvent) -> Option<FsEvent> {
+    let roots = &self.roots;
+    let include = &self.include;
+    let exclude = &self.exclude;
+    event.paths.retain(|path| {
+      (include.is_empty() || include.matches(roots, path))
+        && !exclude.matches(roots, path)
+    });
+    if event.paths.is_empty() {
+      None
+    } else {
+      Some(event)
+    }
+  }
+}
+
+/// Globs given to `include` or `exclude`.
+struct Patterns {
+  /// Relative globs, matched at any depth below a watched path.
+  relative: GlobSet,
+  /// Absolute globs, matched against whole paths.
+  absolute: GlobSet,
+}
+
+impl Patterns {
+  fn new(globs: &[String]) -> Result<Self, AnyError> {
+    let mut relative = GlobSetBuilder::new();
+    let mut absolute = GlobSetBuilder::new();
+    for glob in globs {
+      let invalid = |err: globset::Error| {
+        type_error(format!("Invalid glob \"{}\": {}", glob, err))
+      };
+      let build = |glob: &str| {
+        GlobBuilder::new(glob)
+          .literal_separator(true)
+          .build()
+          .map_err(invalid)
+      };
+      if Path::new(glob).is_absolute() || glob.starts_with('/') {
+        absolute.add(build(glob)?);
+      } else {
+        relative.add(build(&format!("**/{}", glob))?);
+      }
+    }
+    Ok(Self {
+      relative: relative.build()?,
+      absolute: absolute.build()?,
+    })
+  }
+
+  fn is_empty(&self) -> bool {
+    self.relative.is_empty() && self.absolute.is_empty()
+  }
+
+  /// Returns whether a glob matches `path` or one of its parent directories
+  /// inside the watched path that contains it.
+  fn matches(&self, roots: &[PathBuf], path: &Path) -> bool {
+    let (root, relative) = match roots
+      .iter()
+      .filter_map(|root| Some((root, path.strip_prefix(root).ok()?)))
+      .min_by_key(|(_, relative)| relative.components().count())
+    {
+      Some((root, relative)) if !relative.as_os_str().is_empty() => {
+        (root.as_path(), relative)
+      }
+      // A watched file, or anything outside the watched paths, is matched by
+      // its name.
+      _ => match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => (parent, Path::new(name)),
+        _ => return false,
+      },
+    };
+    relative
+      .ancestors()
+      .take_while(|ancestor| !ancestor.as_os_str().is_empty())
+      .any(|ancestor| {
+        self.relative.is_match(ancestor)
+          || self.absolute.is_match(root.join(ancestor))
+      })
+  }
+}
+
+/// What a file looked like when it was last seen, for `dedupe`.
+struct Fingerprint {
+  len: u64,
+  modified: Option<SystemTime>,
+  hash: u64,
+}
+
+impl Fingerprint {
+  /// Takes a fingerprint of `path`, reusing `previous` if the size and
+  /// modification time show that the file can't have changed.
+  fn new(path: &Path, previous: Option<&Fingerprint>) -> Option<Self> {
+    let metadata = std::fs::metadata(path).ok()?;
+    let len = metadata.len();
+    let modified = metadata.modified().ok();
+    if let Some(previous) = previous {
+      if previous.len == len
+        && modified.is_some()
+        && previous.modified == modified
+      {
+        return Some(Self {
+          len,
+          modified,
+          hash: previous.hash,
+        });
+      }
+    }
+    let contents = std::fs::read(path).ok()?;
+    let mut hasher = DefaultHasher::new();
+    hasher.write(&contents);
+    Some(Self {
+      len,
+      modified,
+      hash: hasher.finish(),
+    })
+  }
+}
+
+/// Drops modifications that leave a file's contents unchanged, e.g. an editor
+/// rewriting a file on save or touching its metadata.
+fn dedupe(
+  fingerprints: &mut HashMap<PathBuf, Fingerprint>,
+  mut event: FsEvent,
+) -> Option<FsEvent> {
+  match event.kind.as_str() {
+    "create" => {
+      for path in &event.paths {
+        match Fingerprint::new(path, None) {
+          Some(fingerprint) => fingerprints.insert(path.clone(), fingerprint),
+          None => fingerprints.remove(path),
+        };
+      }
+    }
+    "modify" => event.paths.retain(|path| {
+      let previous = fingerprints.remove(path);
+      match Fingerprint::new(path, previous.as_ref()) {
+        Some(fingerprint) => {
+          let changed =
+            previous.map_or(true, |previous| previous.hash != fingerprint.hash);
+          fingerprints.insert(path.clone(), fingerprint);
+          changed
+        }
+        None => true,
+      }
+    }),
+    "remove" => {
+      for path in &event.paths {
+        fingerprints.remove(path);
+      }
+    }
+    _ => {}
+  }
+  if event.paths.is_empty() {
+    None
+  } else {
+    Some(event)
+  }
+}
+
 #[derive(Deserialize)]
 pub struct OpenArgs {
   recursive: bool,
   paths: Vec<String>,
+  debounce: Option<u64>,
+  #[serde(default)]
+  include: Vec<String>,
+  #[serde(default)]
+  exclude: Vec<String>,
+  #[serde(default)]
+  dedupe: bool,
 }
 
 fn op_fs_events_open(
@@ -99,11 +362,39 @@ fn op_fs_events_open(
   args: OpenArgs,
   _: (),
 ) -> Result<ResourceId, AnyError> {
+  // Event paths are based on the path as given, made absolute or, for some
+  // backends, canonicalized (e.g. under /private on macOS).
+  let mut roots = vec![];
+  for path in &args.paths {
+    let given = PathBuf::from(path);
+    state.borrow_mut::<Permissions>().read.check(&given)?;
+    let absolute = resolve_from_cwd(&given)?;
+    let canonical = absolute.canonicalize().ok();
+    for root in std::iter::once(given)
+      .chain(std::iter::once(absolute))
+      .chain(canonical)
+    {
+      if !roots.contains(&root) {
+        roots.push(root);
+      }
+    }
+  }
+  let filter = EventFilter {
+    roots,
+    include: Patterns::new(&args.include)?,
+    exclude: Patterns::new(&args.exclude)?,
+  };
   let (sender, receiver) = mpsc::channel::<Result<FsEvent, AnyError>>(16);
   let sender = Mutex::new(sender);
   let mut watcher: RecommendedWatcher =
     Watcher::new_immediate(move |res: Result<NotifyEvent, NotifyError>| {
-      let res2 = res.map(FsEvent::from).map_err(AnyError::from);
+      let res2 = match res {
+        Ok(event) => match filter.apply(FsEvent::from(event)) {
+          Some(event) => Ok(event),
+          None => return,
+        },
+        Err(err) => Err(AnyError::from(err)),
+      };
       let sender = sender.lock();
       // Ignore result, if send failed it means that watcher was already closed,
       // but not all messages have been flushed.
@@ -115,15 +406,20 @@ fn op_fs_events_open(
     RecursiveMode::NonRecursive
   };
   for path in &args.paths {
-    state
-      .borrow_mut::<Permissions>()
-      .read
-      .check(&PathBuf::from(path))?;
     watcher.watch(path, recursive_mode)?;
   }
   let resource = FsEventsResource {
     watcher,
-    receiver: AsyncRefCell::new(receiver),
+    queue: AsyncRefCell::new(EventQueue {
+      receiver,
+      pending: VecDeque::new(),
+      fingerprints: if args.dedupe {
+        Some(Default::default())
+      } else {
+        None
+      },
+    }),
+    debounce: args.debounce.map(Duration::from_millis),
     cancel: Default::default(),
   };
   let rid = state.resource_table.add(resource);
@@ -140,12 +436,8 @@ async fn op_fs_events_poll(
     .resource_table
     .get::<FsEventsResource>(rid)
     .ok_or_else(bad_resource_id)?;
-  let mut receiver = RcRef::map(&resource, |r| &r.receiver).borrow_mut().await;
+  let debounce = resource.debounce;
+  let mut queue = RcRef::map(&resource, |r| &r.queue).borrow_mut().await;
   let cancel = RcRef::map(resource, |r| &r.cancel);
-  let maybe_result = receiver.recv().or_cancel(cancel).await?;
-  match maybe_result {
-    Some(Ok(value)) => Ok(Some(value)),
-    Some(Err(err)) => Err(err),
-    None => Ok(None),
-  }
+  queue.next(debounce).or_cancel(cancel).await?
 }
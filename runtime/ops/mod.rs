@@ -1,5 +1,6 @@
 // Copyright 2018-2021 the Deno authors. All rights reserved. MIT license.
 
+pub mod fd_passing;
 pub mod fs;
 pub mod fs_events;
 pub mod http;
@@ -8,6 +9,7 @@ pub mod os;
 pub mod permissions;
 pub mod plugin;
 pub mod process;
+pub mod pty;
 pub mod runtime;
 pub mod signal;
 pub mod tty;
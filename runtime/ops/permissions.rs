@@ -1,5 +1,7 @@
 // Copyright 2018-2021 the Deno authors. All rights reserved. MIT license.
 
+use crate::ops::worker_host::propagate_revocation;
+use crate::permissions::PermissionState;
 use crate::permissions::Permissions;
 use deno_core::error::custom_error;
 use deno_core::error::uri_error;
@@ -21,7 +23,7 @@ pub fn init() -> Extension {
     .build()
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize)]
 pub struct PermissionArgs {
   name: String,
   path: Option<String>,
@@ -64,8 +66,19 @@ pub fn op_query_permission(
 pub fn op_revoke_permission(
   state: &mut OpState,
   args: PermissionArgs,
-  _: (),
+  propagate: Option<bool>,
 ) -> Result<String, AnyError> {
+  let perm = revoke_permission(state, &args)?;
+  if propagate.unwrap_or(true) {
+    propagate_revocation(state, &args);
+  }
+  Ok(perm.to_string())
+}
+
+pub fn revoke_permission(
+  state: &mut OpState,
+  args: &PermissionArgs,
+) -> Result<PermissionState, AnyError> {
   let permissions = state.borrow_mut::<Permissions>();
   let path = args.path.as_deref();
   let perm = match args.name.as_ref() {
@@ -89,7 +102,7 @@ pub fn op_revoke_permission(
       ))
     }
   };
-  Ok(perm.to_string())
+  Ok(perm)
 }
 
 pub fn op_request_permission(
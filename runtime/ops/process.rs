@@ -4,6 +4,8 @@ use super::io::ChildStderrResource;
 use super::io::ChildStdinResource;
 use super::io::ChildStdoutResource;
 use super::io::StdFileResource;
+use super::pty::PtyResource;
+use super::pty::PtySize;
 use crate::permissions::Permissions;
 use deno_core::error::bad_resource_id;
 use deno_core::error::type_error;
@@ -33,6 +35,9 @@ pub fn init() -> Extension {
       ("op_run", op_sync(op_run)),
       ("op_run_status", op_async(op_run_status)),
       ("op_kill", op_sync(op_kill)),
+      ("op_kill_tree", op_sync(op_kill_tree)),
+      ("op_pty_resize", op_sync(op_pty_resize)),
+      ("op_pty_set_raw", op_sync(op_pty_set_raw)),
     ])
     .build()
 }
@@ -68,10 +73,13 @@ pub struct RunArgs {
   stdin_rid: ResourceId,
   stdout_rid: ResourceId,
   stderr_rid: ResourceId,
+  pty: Option<PtySize>,
+  process_group: bool,
 }
 
 struct ChildResource {
   child: AsyncRefCell<tokio::process::Child>,
+  group: Option<ProcessGroup>,
 }
 
 impl Resource for ChildResource {
@@ -86,6 +94,151 @@ impl ChildResource {
   }
 }
 
+/// The process group a child was spawned in, which also contains every
+/// process it spawns in turn.
+#[cfg(unix)]
+struct ProcessGroup {
+  pgid: i32,
+}
+
+#[cfg(unix)]
+impl ProcessGroup {
+  fn new(child: &tokio::process::Child) -> Result<Self, AnyError> {
+    let pid = child.id().expect("child has not been polled yet") as i32;
+    // The child moves itself into its own group before exec. Do the same from
+    // this side, so that the group exists by the time we return; this fails
+    // harmlessly if the child already exec'd.
+    // SAFETY: setpgid has no memory safety requirements.
+    unsafe { libc::setpgid(pid, pid) };
+    Ok(Self { pgid: pid })
+  }
+
+  fn kill(&self, signo: i32) -> Result<(), AnyError> {
+    use nix::sys::signal::{killpg, Signal};
+    use nix::unistd::Pid;
+    use std::convert::TryFrom;
+    let sig = Signal::try_from(signo)?;
+    killpg(Pid::from_raw(self.pgid), Some(sig)).map_err(AnyError::from)
+  }
+}
+
+/// The job object a child was assigned to. Processes spawned by the child are
+/// assigned to it as well. The child is created suspended and only resumed
+/// once it is in the job, so that nothing it spawns can escape the job.
+#[cfg(not(unix))]
+struct ProcessGroup {
+  job: winapi::um::winnt::HANDLE,
+}
+
+#[cfg(not(unix))]
+impl ProcessGroup {
+  fn new(child: &tokio::process::Child) -> Result<Self, AnyError> {
+    use std::io::Error;
+    use winapi::shared::minwindef::DWORD;
+    use winapi::shared::minwindef::FALSE;
+    use winapi::um::handleapi::CloseHandle;
+    use winapi::um::jobapi2::AssignProcessToJobObject;
+    use winapi::um::jobapi2::CreateJobObjectW;
+    use winapi::um::processthreadsapi::OpenProcess;
+    use winapi::um::winnt::PROCESS_SET_QUOTA;
+    use winapi::um::winnt::PROCESS_TERMINATE;
+
+    let job =
+      unsafe { CreateJobObjectW(std::ptr::null_mut(), std::ptr::null()) };
+    if job.is_null() {
+      return Err(Error::last_os_error().into());
+    }
+    let group = Self { job };
+    let pid = child.id().expect("child has not been polled yet");
+    let handle = unsafe {
+      OpenProcess(PROCESS_SET_QUOTA | PROCESS_TERMINATE, FALSE, pid as DWORD)
+    };
+    if handle.is_null() {
+      return Err(Error::last_os_error().into());
+    }
+    let assigned = unsafe { AssignProcessToJobObject(group.job, handle) };
+    let err = Error::last_os_error();
+    unsafe { CloseHandle(handle) };
+    if assigned == FALSE {
+      return Err(err.into());
+    }
+    resume_process(pid)?;
+    Ok(group)
+  }
+
+  fn kill(&self, signal: i32) -> Result<(), AnyError> {
+    use std::io::Error;
+    use winapi::shared::minwindef::FALSE;
+    use winapi::um::jobapi2::TerminateJobObject;
+
+    const SIGINT: i32 = 2;
+    const SIGKILL: i32 = 9;
+    const SIGTERM: i32 = 15;
+
+    if !matches!(signal, SIGINT | SIGKILL | SIGTERM) {
+      return Err(type_error("unsupported signal"));
+    }
+    if unsafe { TerminateJobObject(self.job, 1) } == FALSE {
+      return Err(Error::last_os_error().into());
+    }
+    Ok(())
+  }
+}
+
+/// Resumes the threads of the process `pid`, which was created suspended.
+#[cfg(not(unix))]
+fn resume_process(pid: u32) -> Result<(), AnyError> {
+  use std::io::Error;
+  use std::mem;
+  use winapi::shared::minwindef::DWORD;
+  use winapi::shared::minwindef::FALSE;
+  use winapi::um::handleapi::CloseHandle;
+  use winapi::um::handleapi::INVALID_HANDLE_VALUE;
+  use winapi::um::processthreadsapi::OpenThread;
+  use winapi::um::processthreadsapi::ResumeThread;
+  use winapi::um::tlhelp32::CreateToolhelp32Snapshot;
+  use winapi::um::tlhelp32::Thread32First;
+  use winapi::um::tlhelp32::Thread32Next;
+  use winapi::um::tlhelp32::TH32CS_SNAPTHREAD;
+  use winapi::um::tlhelp32::THREADENTRY32;
+  use winapi::um::winnt::THREAD_SUSPEND_RESUME;
+
+  let snapshot = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0) };
+  if snapshot == INVALID_HANDLE_VALUE {
+    return Err(Error::last_os_error().into());
+  }
+
+  let mut entry: THREADENTRY32 = unsafe { mem::zeroed() };
+  entry.dwSize = mem::size_of::<THREADENTRY32>() as DWORD;
+  let mut result = Ok(());
+  let mut more = unsafe { Thread32First(snapshot, &mut entry) } != FALSE;
+  while more {
+    if entry.th32OwnerProcessID == pid {
+      let thread =
+        unsafe { OpenThread(THREAD_SUSPEND_RESUME, FALSE, entry.th32ThreadID) };
+      // ResumeThread returns (DWORD)-1 on failure.
+      let resumed =
+        !thread.is_null() && unsafe { ResumeThread(thread) } != DWORD::MAX;
+      if !resumed {
+        result = Err(Error::last_os_error().into());
+      }
+      if !thread.is_null() {
+        unsafe { CloseHandle(thread) };
+      }
+    }
+    more = unsafe { Thread32Next(snapshot, &mut entry) } != FALSE;
+  }
+  unsafe { CloseHandle(snapshot) };
+  result
+}
+
+#[cfg(not(unix))]
+impl Drop for ProcessGroup {
+  fn drop(&mut self) {
+    unsafe { winapi::um::handleapi::CloseHandle(self.job) };
+  }
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 // TODO(@AaronO): maybe find a more descriptive name or a convention for return structs
@@ -95,6 +248,7 @@ struct RunInfo {
   stdin_rid: Option<ResourceId>,
   stdout_rid: Option<ResourceId>,
   stderr_rid: Option<ResourceId>,
+  pty_rid: Option<ResourceId>,
 }
 
 fn op_run(
@@ -103,7 +257,10 @@ fn op_run(
   _: (),
 ) -> Result<RunInfo, AnyError> {
   let args = run_args.cmd;
-  state.borrow_mut::<Permissions>().run.check(&args[0])?;
+  state
+    .borrow_mut::<Permissions>()
+    .run
+    .check_args(&args[0], &args[1..])?;
   let env = run_args.env;
   let cwd = run_args.cwd;
 
@@ -117,26 +274,75 @@ fn op_run(
     c.env(key, value);
   }
 
-  // TODO: make this work with other resources, eg. sockets
-  if !run_args.stdin.is_empty() {
-    c.stdin(subprocess_stdio_map(run_args.stdin.as_ref())?);
+  let pty = if let Some(size) = run_args.pty {
+    super::check_unstable(state, "Deno.run.pty");
+    let (pty, slave) = PtyResource::open(size)?;
+    c.stdin(slave.try_clone()?);
+    c.stdout(slave.try_clone()?);
+    c.stderr(slave);
+    #[cfg(unix)]
+    // SAFETY: only async-signal-safe functions are called between fork and
+    // exec.
+    unsafe {
+      c.pre_exec(|| {
+        // Start a new session and make the terminal its controlling
+        // terminal, so that job control and signals like ^C work.
+        if libc::setsid() == -1 || libc::ioctl(0, libc::TIOCSCTTY as _, 0) == -1
+        {
+          return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+      });
+    }
+    Some(pty)
   } else {
-    let file = clone_file(state, run_args.stdin_rid)?;
-    c.stdin(file);
-  }
+    // TODO: make this work with other resources, eg. sockets
+    if !run_args.stdin.is_empty() {
+      c.stdin(subprocess_stdio_map(run_args.stdin.as_ref())?);
+    } else {
+      let file = clone_file(state, run_args.stdin_rid)?;
+      c.stdin(file);
+    }
 
-  if !run_args.stdout.is_empty() {
-    c.stdout(subprocess_stdio_map(run_args.stdout.as_ref())?);
-  } else {
-    let file = clone_file(state, run_args.stdout_rid)?;
-    c.stdout(file);
+    if !run_args.stdout.is_empty() {
+      c.stdout(subprocess_stdio_map(run_args.stdout.as_ref())?);
+    } else {
+      let file = clone_file(state, run_args.stdout_rid)?;
+      c.stdout(file);
+    }
+
+    if !run_args.stderr.is_empty() {
+      c.stderr(subprocess_stdio_map(run_args.stderr.as_ref())?);
+    } else {
+      let file = clone_file(state, run_args.stderr_rid)?;
+      c.stderr(file);
+    }
+    None
+  };
+
+  // A terminal's session is a process group already.
+  #[cfg(unix)]
+  {
+    if run_args.process_group && pty.is_none() {
+      // SAFETY: setpgid is async-signal-safe.
+      unsafe {
+        c.pre_exec(|| {
+          if libc::setpgid(0, 0) == -1 {
+            return Err(std::io::Error::last_os_error());
+          }
+          Ok(())
+        });
+      }
+    }
   }
 
-  if !run_args.stderr.is_empty() {
-    c.stderr(subprocess_stdio_map(run_args.stderr.as_ref())?);
-  } else {
-    let file = clone_file(state, run_args.stderr_rid)?;
-    c.stderr(file);
+  // Keep the child from running until it has been assigned to its job
+  // object.
+  #[cfg(windows)]
+  {
+    if run_args.process_group {
+      c.creation_flags(winapi::um::winbase::CREATE_SUSPENDED);
+    }
   }
 
   // We want to kill child when it's closed
@@ -145,6 +351,21 @@ fn op_run(
   // Spawn the command.
   let mut child = c.spawn()?;
   let pid = child.id();
+  // Drop the command so that its handles to the child's side of the
+  // terminal are closed, otherwise reads would not see the child exit.
+  drop(c);
+  let group = if run_args.process_group {
+    match ProcessGroup::new(&child) {
+      Ok(group) => Some(group),
+      Err(err) => {
+        let _ = child.start_kill();
+        return Err(err);
+      }
+    }
+  } else {
+    None
+  };
+  let pty_rid = pty.map(|pty| state.resource_table.add(pty));
 
   let stdin_rid = match child.stdin.take() {
     Some(child_stdin) => {
@@ -178,6 +399,7 @@ fn op_run(
 
   let child_resource = ChildResource {
     child: AsyncRefCell::new(child),
+    group,
   };
   let child_rid = state.resource_table.add(child_resource);
 
@@ -187,6 +409,7 @@ fn op_run(
     stdin_rid,
     stdout_rid,
     stderr_rid,
+    pty_rid,
   })
 }
 
@@ -293,3 +516,47 @@ fn op_kill(state: &mut OpState, args: KillArgs, _: ()) -> Result<(), AnyError> {
   kill(args.pid, args.signo)?;
   Ok(())
 }
+
+fn op_kill_tree(
+  state: &mut OpState,
+  rid: ResourceId,
+  signo: i32,
+) -> Result<(), AnyError> {
+  super::check_unstable(state, "Deno.Process.killTree");
+  state.borrow_mut::<Permissions>().run.check_all()?;
+
+  let resource = state
+    .resource_table
+    .get::<ChildResource>(rid)
+    .ok_or_else(bad_resource_id)?;
+  match &resource.group {
+    Some(group) => group.kill(signo),
+    None => Err(type_error("Process was not spawned with processGroup")),
+  }
+}
+
+fn op_pty_resize(
+  state: &mut OpState,
+  rid: ResourceId,
+  size: PtySize,
+) -> Result<(), AnyError> {
+  super::check_unstable(state, "Deno.run.pty");
+  let pty = state
+    .resource_table
+    .get::<PtyResource>(rid)
+    .ok_or_else(bad_resource_id)?;
+  pty.resize(size)
+}
+
+fn op_pty_set_raw(
+  state: &mut OpState,
+  rid: ResourceId,
+  raw: bool,
+) -> Result<(), AnyError> {
+  super::check_unstable(state, "Deno.run.pty");
+  let pty = state
+    .resource_table
+    .get::<PtyResource>(rid)
+    .ok_or_else(bad_resource_id)?;
+  pty.set_raw(raw)
+}
@@ -0,0 +1,196 @@
+// Copyright 2018-2021 the Deno authors. All rights reserved. MIT license.
+
+//! Pseudo-terminals for subprocesses spawned with `Deno.run({ pty: true })`.
+
+use deno_core::Resource;
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::rc::Rc;
+
+#[cfg(not(unix))]
+use deno_core::error::not_supported;
+#[cfg(not(unix))]
+use deno_core::error::AnyError;
+#[cfg(not(unix))]
+use deno_core::CancelHandle;
+
+#[derive(Deserialize, Clone, Copy)]
+pub struct PtySize {
+  pub rows: u16,
+  pub cols: u16,
+}
+
+#[cfg(unix)]
+mod unix {
+  use super::PtySize;
+  use deno_core::error::AnyError;
+  use deno_core::CancelHandle;
+  use deno_core::CancelTryFuture;
+  use deno_core::RcRef;
+  use nix::fcntl::fcntl;
+  use nix::fcntl::FcntlArg;
+  use nix::fcntl::FdFlag;
+  use nix::fcntl::OFlag;
+  use nix::pty::openpty;
+  use nix::pty::Winsize;
+  use nix::sys::termios;
+  use std::cell::RefCell;
+  use std::io::Read;
+  use std::io::Write;
+  use std::os::unix::io::AsRawFd;
+  use std::os::unix::io::FromRawFd;
+  use std::rc::Rc;
+  use tokio::io::unix::AsyncFd;
+
+  /// The controlling side of a pseudo-terminal. The child's stdio is attached
+  /// to the other side.
+  pub struct PtyResource {
+    master: AsyncFd<std::fs::File>,
+    /// Terminal attributes from before raw mode was enabled.
+    cooked: RefCell<Option<termios::Termios>>,
+    pub(super) cancel: CancelHandle,
+  }
+
+  fn winsize(size: PtySize) -> Winsize {
+    Winsize {
+      ws_row: size.rows,
+      ws_col: size.cols,
+      ws_xpixel: 0,
+      ws_ypixel: 0,
+    }
+  }
+
+  impl PtyResource {
+    /// Opens a pseudo-terminal, returning the resource for its controlling
+    /// side and the file for the child's side.
+    pub fn open(size: PtySize) -> Result<(Self, std::fs::File), AnyError> {
+      let pty = openpty(Some(&winsize(size)), None::<&termios::Termios>)?;
+      let master = unsafe { std::fs::File::from_raw_fd(pty.master) };
+      let slave = unsafe { std::fs::File::from_raw_fd(pty.slave) };
+      for fd in &[pty.master, pty.slave] {
+        fcntl(*fd, FcntlArg::F_SETFD(FdFlag::FD_CLOEXEC))?;
+      }
+      fcntl(pty.master, FcntlArg::F_SETFL(OFlag::O_NONBLOCK))?;
+
+      let resource = Self {
+        master: AsyncFd::new(master)?,
+        cooked: RefCell::new(None),
+        cancel: Default::default(),
+      };
+      Ok((resource, slave))
+    }
+
+    pub async fn read(
+      self: &Rc<Self>,
+      buf: &mut [u8],
+    ) -> Result<usize, AnyError> {
+      let cancel = RcRef::map(self, |r| &r.cancel);
+      let read = async {
+        loop {
+          let mut guard = self.master.readable().await?;
+          match guard.try_io(|fd| fd.get_ref().read(buf)) {
+            Ok(Ok(nread)) => return Ok(nread),
+            // Linux reports EIO once every handle to the child's side of the
+            // terminal is closed, i.e. the child has exited.
+            Ok(Err(err)) if err.raw_os_error() == Some(libc::EIO) => {
+              return Ok(0)
+            }
+            Ok(Err(err)) => return Err(err),
+            Err(_would_block) => continue,
+          }
+        }
+      };
+      let nread = read.try_or_cancel(cancel).await?;
+      Ok(nread)
+    }
+
+    pub async fn write(self: &Rc<Self>, buf: &[u8]) -> Result<usize, AnyError> {
+      let cancel = RcRef::map(self, |r| &r.cancel);
+      let write = async {
+        loop {
+          let mut guard = self.master.writable().await?;
+          match guard.try_io(|fd| fd.get_ref().write(buf)) {
+            Ok(result) => return result,
+            Err(_would_block) => continue,
+          }
+        }
+      };
+      let nwritten = write.try_or_cancel(cancel).await?;
+      Ok(nwritten)
+    }
+
+    pub fn resize(&self, size: PtySize) -> Result<(), AnyError> {
+      let winsize = winsize(size);
+      let fd = self.master.get_ref().as_raw_fd();
+      // SAFETY: `fd` is open for the lifetime of `self` and `winsize` is a
+      // valid `struct winsize`.
+      let result = unsafe { libc::ioctl(fd, libc::TIOCSWINSZ as _, &winsize) };
+      if result == -1 {
+        return Err(std::io::Error::last_os_error().into());
+      }
+      Ok(())
+    }
+
+    pub fn set_raw(&self, raw: bool) -> Result<(), AnyError> {
+      let fd = self.master.get_ref().as_raw_fd();
+      let mut cooked = self.cooked.borrow_mut();
+      if raw {
+        if cooked.is_some() {
+          return Ok(());
+        }
+        let original = termios::tcgetattr(fd)?;
+        let mut attrs = original.clone();
+        termios::cfmakeraw(&mut attrs);
+        termios::tcsetattr(fd, termios::SetArg::TCSADRAIN, &attrs)?;
+        *cooked = Some(original);
+      } else if let Some(original) = cooked.take() {
+        termios::tcsetattr(fd, termios::SetArg::TCSADRAIN, &original)?;
+      }
+      Ok(())
+    }
+  }
+}
+
+#[cfg(unix)]
+pub use unix::PtyResource;
+
+#[cfg(not(unix))]
+pub struct PtyResource {
+  cancel: CancelHandle,
+}
+
+#[cfg(not(unix))]
+impl PtyResource {
+  pub fn open(_size: PtySize) -> Result<(Self, std::fs::File), AnyError> {
+    Err(not_supported())
+  }
+
+  pub async fn read(
+    self: &Rc<Self>,
+    _buf: &mut [u8],
+  ) -> Result<usize, AnyError> {
+    Err(not_supported())
+  }
+
+  pub async fn write(self: &Rc<Self>, _buf: &[u8]) -> Result<usize, AnyError> {
+    Err(not_supported())
+  }
+
+  pub fn resize(&self, _size: PtySize) -> Result<(), AnyError> {
+    Err(not_supported())
+  }
+
+  pub fn set_raw(&self, _raw: bool) -> Result<(), AnyError> {
+    Err(not_supported())
+  }
+}
+
+impl Resource for PtyResource {
+  fn name(&self) -> Cow<str> {
+    "pty".into()
+  }
+
+  fn close(self: Rc<Self>) {
+    self.cancel.cancel();
+  }
+}
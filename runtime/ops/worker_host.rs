@@ -1,5 +1,6 @@
 // Copyright 2018-2021 the Deno authors. All rights reserved. MIT license.
 
+use crate::ops::permissions::PermissionArgs;
 use crate::permissions::resolve_read_allowlist;
 use crate::permissions::resolve_write_allowlist;
 use crate::permissions::EnvDescriptor;
@@ -67,6 +68,16 @@ pub struct WorkerThread {
 
 pub type WorkersTable = HashMap<WorkerId, WorkerThread>;
 
+/// Forwards a permission revocation to every child worker, which applies it
+/// to its own permissions and in turn forwards it to its children.
+pub fn propagate_revocation(state: &OpState, args: &PermissionArgs) {
+  if let Some(workers) = state.try_borrow::<WorkersTable>() {
+    for worker_thread in workers.values() {
+      worker_thread.worker_handle.revoke_permission(args.clone());
+    }
+  }
+}
+
 pub fn init(create_web_worker_cb: Arc<CreateWebWorkerCb>) -> Extension {
   Extension::builder()
     .state(move |state| {
@@ -204,7 +215,10 @@ fn merge_run_permission(
 ) -> Result<UnaryPermission<RunDescriptor>, AnyError> {
   if let Some(worker) = worker {
     if (worker.global_state < main.global_state)
-      || !worker.granted_list.iter().all(|x| main.check(&x.0).is_ok())
+      || !worker
+        .granted_list
+        .iter()
+        .all(|x| main.check_descriptor(x).is_ok())
     {
       return Err(custom_error(
         "PermissionDenied",
@@ -12,12 +12,15 @@ use deno_core::serde::Serialize;
 use deno_core::url;
 use deno_core::ModuleSpecifier;
 use deno_core::OpState;
+use ipnet::IpNet;
 use log::debug;
 use std::collections::HashSet;
 use std::fmt;
 use std::hash::Hash;
 #[cfg(not(test))]
 use std::io;
+use std::net::IpAddr;
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 #[cfg(test)]
 use std::sync::atomic::AtomicBool;
@@ -180,11 +183,43 @@ impl NetDescriptor {
   }
 
   pub fn from_string(host: String) -> Self {
+    if host.parse::<IpNet>().is_ok() || Self::is_vsock(&host) {
+      return NetDescriptor(host, None);
+    }
     let url = url::Url::parse(&format!("http://{}", host)).unwrap();
     let hostname = url.host_str().unwrap().to_string();
 
     NetDescriptor(hostname, url.port())
   }
+
+  /// Returns true for vsock descriptors: `vsock`, `vsock:<cid>` or
+  /// `vsock:<cid>:<port>`. Their ports don't fit a `u16`, so they are kept
+  /// whole in the host part.
+  fn is_vsock(host: &str) -> bool {
+    host == "vsock" || host.starts_with("vsock:")
+  }
+
+  /// Returns true if this descriptor is a CIDR range, e.g. `10.0.0.0/8`.
+  fn is_cidr(&self) -> bool {
+    self.0.parse::<IpNet>().is_ok()
+  }
+
+  /// Returns true if this descriptor is a CIDR range (e.g. `10.0.0.0/8`) that
+  /// contains the given IP address host.
+  fn contains<T: AsRef<str>>(&self, host: &(T, Option<u16>)) -> bool {
+    let ip = host
+      .0
+      .as_ref()
+      .trim_start_matches('[')
+      .trim_end_matches(']')
+      .parse::<IpAddr>();
+    match (self.0.parse::<IpNet>(), ip) {
+      (Ok(net), Ok(ip)) => {
+        net.contains(&ip) && (self.1.is_none() || self.1 == host.1)
+      }
+      _ => false,
+    }
+  }
 }
 
 impl fmt::Display for NetDescriptor {
@@ -202,6 +237,48 @@ pub struct EnvDescriptor(pub String);
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Default, Deserialize)]
 pub struct RunDescriptor(pub String);
 
+impl RunDescriptor {
+  /// Splits a descriptor of the form `cmd:arg1 arg2|arg3` into the command and
+  /// the alternative argument prefixes it is restricted to. A leading Windows
+  /// drive letter (`C:\`) is not treated as a separator.
+  fn parse(&self) -> (&str, Option<Vec<Vec<&str>>>) {
+    let bytes = self.0.as_bytes();
+    let offset = if bytes.len() > 2
+      && bytes[0].is_ascii_alphabetic()
+      && bytes[1] == b':'
+      && (bytes[2] == b'\\' || bytes[2] == b'/')
+    {
+      2
+    } else {
+      0
+    };
+    match self.0[offset..].find(':') {
+      Some(i) => (
+        &self.0[..offset + i],
+        Some(
+          self.0[offset + i + 1..]
+            .split('|')
+            .map(|prefix| prefix.split_whitespace().collect())
+            .collect(),
+        ),
+      ),
+      None => (&self.0, None),
+    }
+  }
+
+  fn matches(&self, cmd: &str, args: &[String]) -> bool {
+    let (cmd_, prefix) = self.parse();
+    cmd_ == cmd
+      && match prefix {
+        None => true,
+        Some(prefixes) => prefixes.iter().any(|prefix| {
+          args.len() >= prefix.len()
+            && prefix.iter().zip(args).all(|(a, b)| *a == b)
+        }),
+      }
+  }
+}
+
 impl UnaryPermission<ReadDescriptor> {
   pub fn query(&self, path: Option<&Path>) -> PermissionState {
     let path = path.map(|p| resolve_from_cwd(p).unwrap());
@@ -461,6 +538,7 @@ impl UnaryPermission<NetDescriptor> {
             None,
           )))
             || self.granted_list.contains(&NetDescriptor::new(host))
+            || self.granted_list.iter().any(|host_| host_.contains(host))
         }
       }
     {
@@ -551,6 +629,70 @@ impl UnaryPermission<NetDescriptor> {
     result
   }
 
+  /// Returns true if `host` isn't allowed by name but an allowed CIDR range
+  /// might contain its address. Hosts should only be resolved before being
+  /// checked in that case, so that lookups can't happen without permission.
+  pub fn needs_resolution<T: AsRef<str>>(
+    &self,
+    host: &(T, Option<u16>),
+  ) -> bool {
+    self.query(Some(host)) == PermissionState::Prompt
+      && self.granted_list.iter().any(NetDescriptor::is_cidr)
+  }
+
+  /// As `check()`, but grants access if the address `host` resolved to falls
+  /// within an allowed CIDR range, even if the hostname itself is not allowed.
+  /// Plain IP entries only ever match IP literals.
+  pub fn check_resolved<T: AsRef<str>>(
+    &mut self,
+    host: &(T, Option<u16>),
+    addr: &SocketAddr,
+  ) -> Result<(), AnyError> {
+    let ip = &(addr.ip().to_string(), host.1);
+    if self.query(Some(host)) == PermissionState::Prompt
+      && self.query(Some(ip)) != PermissionState::Denied
+      && self.granted_list.iter().any(|host_| host_.contains(ip))
+    {
+      return Ok(());
+    }
+    self.check(host)
+  }
+
+  /// Checks access to a vsock address. It is granted by `vsock`, by
+  /// `vsock:<cid>` or by `vsock:<cid>:<port>`.
+  pub fn check_vsock(&mut self, cid: u32, port: u32) -> Result<(), AnyError> {
+    let descriptors = [
+      NetDescriptor("vsock".to_string(), None),
+      NetDescriptor(format!("vsock:{}", cid), None),
+      NetDescriptor(format!("vsock:{}:{}", cid, port), None),
+    ];
+    let state = if self.global_state == PermissionState::Denied
+      && descriptors.iter().any(|d| self.denied_list.contains(d))
+    {
+      PermissionState::Denied
+    } else if self.global_state == PermissionState::Granted
+      || descriptors.iter().any(|d| self.granted_list.contains(d))
+    {
+      PermissionState::Granted
+    } else {
+      PermissionState::Prompt
+    };
+    let [_, _, new_host] = descriptors;
+    let (result, prompted) =
+      state.check(self.name, Some(&format!("\"{}\"", new_host)), self.prompt);
+    if prompted {
+      if result.is_ok() {
+        self.granted_list.insert(new_host);
+      } else {
+        self.denied_list.insert(new_host);
+        self.global_state = PermissionState::Denied;
+      }
+    }
+    result
+  }
+
+  /// Unlike `check_resolved()`, this only applies CIDR ranges to IP literals:
+  /// `fetch()` and `WebSocket` leave name resolution to the HTTP client.
   pub fn check_url(&mut self, url: &url::Url) -> Result<(), AnyError> {
     let hostname = url
       .host_str()
@@ -746,7 +888,9 @@ impl UnaryPermission<RunDescriptor> {
 
   pub fn revoke(&mut self, cmd: Option<&str>) -> PermissionState {
     if let Some(cmd) = cmd {
-      self.granted_list.retain(|cmd_| cmd_.0 != cmd);
+      self
+        .granted_list
+        .retain(|cmd_| cmd_.0 != cmd && cmd_.parse().0 != cmd);
     } else {
       self.granted_list.clear();
       if self.global_state == PermissionState::Granted {
@@ -757,11 +901,26 @@ impl UnaryPermission<RunDescriptor> {
   }
 
   pub fn check(&mut self, cmd: &str) -> Result<(), AnyError> {
-    let (result, prompted) = self.query(Some(cmd)).check(
-      self.name,
-      Some(&format!("\"{}\"", cmd)),
-      self.prompt,
-    );
+    self.check_args(cmd, &[])
+  }
+
+  /// As `check()`, but also considers grants that restrict `cmd` to a given
+  /// argument prefix, e.g. `--allow-run=git:status`.
+  pub fn check_args(
+    &mut self,
+    cmd: &str,
+    args: &[String],
+  ) -> Result<(), AnyError> {
+    let state = match self.query(Some(cmd)) {
+      PermissionState::Prompt
+        if self.granted_list.iter().any(|cmd_| cmd_.matches(cmd, args)) =>
+      {
+        PermissionState::Granted
+      }
+      state => state,
+    };
+    let (result, prompted) =
+      state.check(self.name, Some(&format!("\"{}\"", cmd)), self.prompt);
     if prompted {
       if result.is_ok() {
         self.granted_list.insert(RunDescriptor(cmd.to_string()));
@@ -773,6 +932,22 @@ impl UnaryPermission<RunDescriptor> {
     result
   }
 
+  /// As `check_args()`, for a descriptor that may itself be restricted to
+  /// argument prefixes: `git:push` is allowed by a `git` grant, but `git` is
+  /// not allowed by `git:push`. Each alternative prefix must be allowed.
+  pub fn check_descriptor(
+    &mut self,
+    descriptor: &RunDescriptor,
+  ) -> Result<(), AnyError> {
+    let (cmd, prefixes) = descriptor.parse();
+    for prefix in prefixes.unwrap_or_else(|| vec![vec![]]) {
+      let args: Vec<String> =
+        prefix.into_iter().map(ToString::to_string).collect();
+      self.check_args(cmd, &args)?;
+    }
+    Ok(())
+  }
+
   pub fn check_all(&mut self) -> Result<(), AnyError> {
     let (result, prompted) =
       self.query(None).check(self.name, Some("all"), self.prompt);
@@ -970,6 +1145,22 @@ impl deno_net::NetPermissions for Permissions {
     self.net.check(host)
   }
 
+  fn needs_resolution<T: AsRef<str>>(&self, host: &(T, Option<u16>)) -> bool {
+    self.net.needs_resolution(host)
+  }
+
+  fn check_net_resolved<T: AsRef<str>>(
+    &mut self,
+    host: &(T, Option<u16>),
+    addr: &SocketAddr,
+  ) -> Result<(), AnyError> {
+    self.net.check_resolved(host, addr)
+  }
+
+  fn check_vsock(&mut self, cid: u32, port: u32) -> Result<(), AnyError> {
+    self.net.check_vsock(cid, port)
+  }
+
   fn check_read(&mut self, path: &Path) -> Result<(), AnyError> {
     self.read.check(path)
   }
@@ -1722,4 +1913,115 @@ mod tests {
       PermissionState::Prompt
     );
   }
+
+  #[test]
+  fn test_run_args() {
+    let mut perms = Permissions::allow_all();
+    perms.run = Permissions::new_run(
+      &Some(svec!["git:status", "git:remote show", "C:\\git.exe"]),
+      false,
+    );
+
+    assert!(perms.run.check_args("git", &svec!["status"]).is_ok());
+    assert!(perms.run.check_args("git", &svec!["status", "-s"]).is_ok());
+    assert!(perms
+      .run
+      .check_args("git", &svec!["remote", "show"])
+      .is_ok());
+    assert!(perms
+      .run
+      .check_args("git", &svec!["remote", "add"])
+      .is_err());
+    assert!(perms.run.check_args("git", &svec!["push"]).is_err());
+    assert!(perms.run.check("git").is_err());
+    assert!(perms.run.check("C:\\git.exe").is_ok());
+
+    assert_eq!(
+      perms.run.query(Some(&"git:status".to_string())),
+      PermissionState::Granted
+    );
+    assert_eq!(
+      perms.run.revoke(Some(&"git".to_string())),
+      PermissionState::Prompt
+    );
+    assert!(perms.run.check_args("git", &svec!["status"]).is_err());
+
+    perms.run = Permissions::new_run(&Some(svec!["git:status|log"]), false);
+    assert!(perms.run.check_args("git", &svec!["status"]).is_ok());
+    assert!(perms.run.check_args("git", &svec!["log", "-1"]).is_ok());
+    assert!(perms.run.check_args("git", &svec!["push"]).is_err());
+    assert!(perms.run.check("log").is_err());
+
+    perms.run = Permissions::new_run(&Some(svec!["git", "npm:ci|test"]), false);
+    let git_push = RunDescriptor("git:push".to_string());
+    let npm_ci = RunDescriptor("npm:ci --silent".to_string());
+    let npm_ci_test = RunDescriptor("npm:ci|test".to_string());
+    let npm_ci_start = RunDescriptor("npm:ci|start".to_string());
+    let npm = RunDescriptor("npm".to_string());
+    assert!(perms.run.check_descriptor(&git_push).is_ok());
+    assert!(perms.run.check_descriptor(&npm_ci).is_ok());
+    assert!(perms.run.check_descriptor(&npm_ci_test).is_ok());
+    assert!(perms.run.check_descriptor(&npm_ci_start).is_err());
+    assert!(perms.run.check_descriptor(&npm).is_err());
+  }
+
+  #[test]
+  fn test_net_cidr() {
+    let mut perms = Permissions::allow_all();
+    perms.net = Permissions::new_net(
+      &Some(svec!["10.0.0.0/8", "192.168.1.0/24", "fd00::/8"]),
+      false,
+    );
+
+    assert!(perms.net.check(&("10.1.2.3", Some(80))).is_ok());
+    assert!(perms.net.check(&("192.168.1.254", None)).is_ok());
+    assert!(perms.net.check(&("[fd00::1]", Some(443))).is_ok());
+    assert!(perms.net.check(&("192.168.2.1", Some(80))).is_err());
+    assert!(perms.net.check(&("11.0.0.1", Some(80))).is_err());
+
+    let internal: SocketAddr = "10.0.0.5:443".parse().unwrap();
+    let external: SocketAddr = "93.184.216.34:443".parse().unwrap();
+    assert!(perms
+      .net
+      .check_resolved(&("internal.example", Some(443)), &internal)
+      .is_ok());
+    assert!(perms
+      .net
+      .check_resolved(&("example.com", Some(443)), &external)
+      .is_err());
+    assert!(perms.net.needs_resolution(&("internal.example", Some(443))));
+    assert!(!perms.net.needs_resolution(&("10.1.2.3", Some(443))));
+
+    // Plain IP entries don't allow hostnames that resolve to them, and
+    // nothing needs resolving if no ranges are allowed.
+    perms.net = Permissions::new_net(&Some(svec!["10.0.0.5"]), false);
+    assert!(!perms.net.needs_resolution(&("internal.example", Some(443))));
+    assert!(perms
+      .net
+      .check_resolved(&("internal.example", Some(443)), &internal)
+      .is_err());
+  }
+
+  #[test]
+  fn test_net_vsock() {
+    let mut perms = Permissions::allow_all();
+    perms.net = Permissions::new_net(
+      &Some(svec!["vsock:2", "vsock:3:5000", "localhost"]),
+      false,
+    );
+
+    assert!(perms.net.check_vsock(2, 1).is_ok());
+    assert!(perms.net.check_vsock(2, 70000).is_ok());
+    assert!(perms.net.check_vsock(3, 5000).is_ok());
+    assert!(perms.net.check_vsock(3, 5001).is_err());
+    assert!(perms.net.check_vsock(1, 5000).is_err());
+    // vsock entries don't grant hostnames and vice versa.
+    assert!(perms.net.check(&("vsock", None)).is_err());
+
+    perms.net = Permissions::new_net(&Some(svec!["vsock"]), false);
+    assert!(perms.net.check_vsock(1, 5000).is_ok());
+
+    perms.net = Permissions::new_net(&Some(svec!["localhost"]), false);
+    assert!(perms.net.check_vsock(1, 5000).is_err());
+  }
 }
@@ -4,6 +4,7 @@ use crate::inspector_server::InspectorServer;
 use crate::js;
 use crate::metrics;
 use crate::ops;
+use crate::ops::permissions::PermissionArgs;
 use crate::permissions::Permissions;
 use crate::tokio_util::create_basic_runtime;
 use deno_broadcast_channel::InMemoryBroadcastChannel;
@@ -155,6 +156,7 @@ impl WebWorkerInternalHandle {
 pub struct SendableWebWorkerHandle {
   port: MessagePort,
   receiver: mpsc::Receiver<WorkerControlEvent>,
+  revocation_sender: mpsc::UnboundedSender<PermissionArgs>,
   terminated: Arc<AtomicBool>,
   isolate_handle: v8::IsolateHandle,
 }
@@ -164,6 +166,7 @@ impl From<SendableWebWorkerHandle> for WebWorkerHandle {
     WebWorkerHandle {
       receiver: Rc::new(RefCell::new(handle.receiver)),
       port: Rc::new(handle.port),
+      revocation_sender: handle.revocation_sender,
       terminated: handle.terminated,
       isolate_handle: handle.isolate_handle,
     }
@@ -181,6 +184,7 @@ impl From<SendableWebWorkerHandle> for WebWorkerHandle {
 pub struct WebWorkerHandle {
   pub port: Rc<MessagePort>,
   receiver: Rc<RefCell<mpsc::Receiver<WorkerControlEvent>>>,
+  revocation_sender: mpsc::UnboundedSender<PermissionArgs>,
   terminated: Arc<AtomicBool>,
   isolate_handle: v8::IsolateHandle,
 }
@@ -195,6 +199,14 @@ impl WebWorkerHandle {
     Ok(receiver.next().await)
   }
 
+  /// Forward a permission revocation to the worker, which applies it the next
+  /// time its event loop is polled
+  pub fn revoke_permission(&self, args: PermissionArgs) {
+    // If the channel is closed the worker has already shut down, so there is
+    // nothing left to revoke.
+    let _ = self.revocation_sender.unbounded_send(args);
+  }
+
   /// Terminate the worker
   /// This function will set terminated to true, terminate the isolate and close the message channel
   pub fn terminate(self) {
@@ -214,9 +226,14 @@ impl WebWorkerHandle {
 
 fn create_handles(
   isolate_handle: v8::IsolateHandle,
-) -> (WebWorkerInternalHandle, SendableWebWorkerHandle) {
+) -> (
+  WebWorkerInternalHandle,
+  SendableWebWorkerHandle,
+  mpsc::UnboundedReceiver<PermissionArgs>,
+) {
   let (parent_port, worker_port) = create_entangled_message_port();
   let (ctrl_tx, ctrl_rx) = mpsc::channel::<WorkerControlEvent>(1);
+  let (revocation_tx, revocation_rx) = mpsc::unbounded::<PermissionArgs>();
   let terminated = Arc::new(AtomicBool::new(false));
   let internal_handle = WebWorkerInternalHandle {
     sender: ctrl_tx,
@@ -228,10 +245,11 @@ fn create_handles(
   let external_handle = SendableWebWorkerHandle {
     receiver: ctrl_rx,
     port: worker_port,
+    revocation_sender: revocation_tx,
     terminated,
     isolate_handle,
   };
-  (internal_handle, external_handle)
+  (internal_handle, external_handle, revocation_rx)
 }
 
 /// This struct is an implementation of `Worker` Web API
@@ -243,6 +261,7 @@ pub struct WebWorker {
   pub js_runtime: JsRuntime,
   pub name: String,
   internal_handle: WebWorkerInternalHandle,
+  revocation_receiver: mpsc::UnboundedReceiver<PermissionArgs>,
   pub use_deno_namespace: bool,
   pub main_module: ModuleSpecifier,
 }
@@ -341,6 +360,7 @@ impl WebWorker {
         ops::tty::init(),
         deno_http::init(),
         ops::http::init(),
+        ops::fd_passing::init(),
         ops::io::init_stdio(),
       ]
     } else {
@@ -372,13 +392,14 @@ impl WebWorker {
       );
     }
 
-    let (internal_handle, external_handle) = {
+    let (internal_handle, external_handle, revocation_receiver) = {
       let handle = js_runtime.v8_isolate().thread_safe_handle();
-      let (internal_handle, external_handle) = create_handles(handle);
+      let (internal_handle, external_handle, revocation_receiver) =
+        create_handles(handle);
       let op_state = js_runtime.op_state();
       let mut op_state = op_state.borrow_mut();
       op_state.put(internal_handle.clone());
-      (internal_handle, external_handle)
+      (internal_handle, external_handle, revocation_receiver)
     };
 
     (
@@ -387,6 +408,7 @@ impl WebWorker {
         js_runtime,
         name,
         internal_handle,
+        revocation_receiver,
         use_deno_namespace: options.use_deno_namespace,
         main_module,
       },
@@ -480,6 +502,18 @@ impl WebWorker {
       return Poll::Ready(Ok(()));
     }
 
+    // Apply revocations forwarded by the parent before running any more JS,
+    // and pass them on to this worker's own children.
+    while let Poll::Ready(Some(args)) =
+      self.revocation_receiver.poll_next_unpin(cx)
+    {
+      let op_state = self.js_runtime.op_state();
+      let mut op_state = op_state.borrow_mut();
+      if ops::permissions::revoke_permission(&mut op_state, &args).is_ok() {
+        ops::worker_host::propagate_revocation(&op_state, &args);
+      }
+    }
+
     match self.js_runtime.poll_event_loop(cx, wait_for_inspector) {
       Poll::Ready(r) => {
         // If js ended because we are terminating, just return Ok
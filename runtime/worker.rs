@@ -132,6 +132,7 @@ impl MainWorker {
       ops::tty::init(),
       deno_http::init(),
       ops::http::init(),
+      ops::fd_passing::init(),
       // Permissions ext (worker specific state)
       perm_ext,
     ];